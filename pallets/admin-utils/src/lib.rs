@@ -33,7 +33,7 @@ pub mod pallet {
     use pallet_evm_chain_id::{self, ChainId};
     use pallet_subtensor::utils::rate_limiting::TransactionType;
     use sp_runtime::BoundedVec;
-    use substrate_fixed::types::I96F32;
+    use substrate_fixed::types::{I96F32, U64F64, U96F32};
 
     /// The main data structure of the module.
     #[pallet::pallet]
@@ -1476,6 +1476,573 @@ pub mod pallet {
             );
             Ok(())
         }
+
+        /// Sets the block until which staking on a subnet is fee free.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `block` - The first block at which staking fees apply again.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(66)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_fee_holiday_until(
+            origin: OriginFor<T>,
+            netuid: u16,
+            block: u64,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_fee_holiday_until(netuid, block);
+
+            log::debug!(
+                "FeeHolidayUntilSet( netuid: {:?}, block: {:?} )",
+                netuid,
+                block
+            );
+            Ok(())
+        }
+
+        /// Enables or disables the dividend based dynamic staking fee for a subnet.
+        ///
+        /// When disabled, unstaking and moving stake out of the subnet pays the flat
+        /// `DefaultStakingFee`.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `enabled` - Whether the dynamic fee is enabled.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(68)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_dynamic_fee_enabled(
+            origin: OriginFor<T>,
+            netuid: u16,
+            enabled: bool,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_dynamic_fee_enabled(netuid, enabled);
+
+            log::debug!(
+                "DynamicFeeEnabledSet( netuid: {:?}, enabled: {:?} )",
+                netuid,
+                enabled
+            );
+            Ok(())
+        }
+
+        /// Sets the minimum number of shares a stake must add to a hotkey's pool on a subnet.
+        ///
+        /// Stakes that would mint fewer shares are rejected, which protects high-value
+        /// subnets from share precision attacks. Zero only requires a non-zero increment.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `min_share_increment` - The minimum share increment.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        /// * `SubnetNotExists` - If the subnet does not exist.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(69)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_min_share_increment(
+            origin: OriginFor<T>,
+            netuid: u16,
+            min_share_increment: U64F64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            pallet_subtensor::Pallet::<T>::set_min_share_increment(netuid, min_share_increment);
+
+            log::debug!(
+                "MinShareIncrementSet( netuid: {:?}, min_share_increment: {:?} )",
+                netuid,
+                min_share_increment
+            );
+            Ok(())
+        }
+
+        /// Sets the number of epochs for which hotkey alpha snapshots are retained.
+        ///
+        /// A retention of zero disables snapshots.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `epochs` - The number of epochs to retain.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(70)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_hotkey_alpha_snapshot_retention(
+            origin: OriginFor<T>,
+            epochs: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_hotkey_alpha_snapshot_retention(epochs);
+
+            log::debug!("HotkeyAlphaSnapshotRetentionSet( epochs: {:?} )", epochs);
+            Ok(())
+        }
+
+        /// Sets the share of the staking fee that is burned on a subnet instead of being
+        /// added to its TAO reserve.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `burn_bps` - The burned share in basis points, at most 10000.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        /// * `InvalidFeeBurnBps` - If the share exceeds 10000 basis points.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(71)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_fee_burn_bps(
+            origin: OriginFor<T>,
+            netuid: u16,
+            burn_bps: u16,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            ensure!(
+                burn_bps <= 10_000,
+                pallet_subtensor::Error::<T>::InvalidFeeBurnBps
+            );
+            pallet_subtensor::Pallet::<T>::set_fee_burn_bps(netuid, burn_bps);
+
+            log::debug!(
+                "FeeBurnBpsSet( netuid: {:?}, burn_bps: {:?} )",
+                netuid,
+                burn_bps
+            );
+            Ok(())
+        }
+
+        /// Sets the multiplier applied to the staking fee when removing stake from a subnet.
+        ///
+        /// A multiplier above one makes exits more expensive than entries. The fee never drops
+        /// below `DefaultStakingFee`.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `multiplier` - The unstake fee multiplier, at most `MAX_UNSTAKE_FEE_MULTIPLIER`.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        /// * `InvalidUnstakeFeeMultiplier` - If the multiplier exceeds `MAX_UNSTAKE_FEE_MULTIPLIER`.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(72)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_unstake_fee_multiplier(
+            origin: OriginFor<T>,
+            netuid: u16,
+            multiplier: U96F32,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            ensure!(
+                multiplier
+                    <= U96F32::saturating_from_num(pallet_subtensor::MAX_UNSTAKE_FEE_MULTIPLIER),
+                pallet_subtensor::Error::<T>::InvalidUnstakeFeeMultiplier
+            );
+            pallet_subtensor::Pallet::<T>::set_unstake_fee_multiplier(netuid, multiplier);
+
+            log::debug!(
+                "UnstakeFeeMultiplierSet( netuid: {:?}, multiplier: {:?} )",
+                netuid,
+                multiplier
+            );
+            Ok(())
+        }
+
+        /// Sets the number of blocks a coldkey must wait between stakes to the same hotkey.
+        ///
+        /// A cooldown of zero disables the check.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `cooldown_blocks` - The stake cooldown in blocks.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(73)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_stake_cooldown_blocks(
+            origin: OriginFor<T>,
+            netuid: u16,
+            cooldown_blocks: u32,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_stake_cooldown_blocks(netuid, cooldown_blocks);
+
+            log::debug!(
+                "StakeCooldownBlocksSet( netuid: {:?}, cooldown_blocks: {:?} )",
+                netuid,
+                cooldown_blocks
+            );
+            Ok(())
+        }
+
+        /// Sets or clears the minimum liquidity that swaps must leave in a subnet's pool.
+        ///
+        /// When cleared, the global `DefaultMinimumPoolLiquidity` applies.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `min_liquidity` - The minimum pool liquidity, or `None` to clear the override.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(74)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_min_pool_liquidity_override(
+            origin: OriginFor<T>,
+            netuid: u16,
+            min_liquidity: Option<u64>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_min_pool_liquidity_override(netuid, min_liquidity);
+
+            log::debug!(
+                "MinPoolLiquidityOverrideSet( netuid: {:?}, min_liquidity: {:?} )",
+                netuid,
+                min_liquidity
+            );
+            Ok(())
+        }
+
+        /// Enables or disables forced partial fills of limit orders on a subnet.
+        ///
+        /// When enabled, `allow_partial` is treated as true for every limit order on the subnet.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `force` - Whether limit orders always allow partial fills.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(75)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_force_partial_fills(
+            origin: OriginFor<T>,
+            netuid: u16,
+            force: bool,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_force_partial_fills(netuid, force);
+
+            log::debug!(
+                "ForcePartialFillsSet( netuid: {:?}, force: {:?} )",
+                netuid,
+                force
+            );
+            Ok(())
+        }
+
+        /// Enables or disables redistributing staking fees to stakers on a subnet.
+        ///
+        /// When enabled, the unburned part of each staking fee is swapped for alpha and shared
+        /// among the stakers of the hotkey instead of being added to the TAO reserve.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `enabled` - Whether staking fees are redistributed to stakers.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(76)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_fee_redistribution_enabled(
+            origin: OriginFor<T>,
+            netuid: u16,
+            enabled: bool,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_fee_redistribution_enabled(netuid, enabled);
+
+            log::debug!(
+                "FeeRedistributionEnabledSet( netuid: {:?}, enabled: {:?} )",
+                netuid,
+                enabled
+            );
+            Ok(())
+        }
+
+        /// Enables or disables the debug event emitted by every moving price update.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `enabled` - Whether moving price updates emit `MovingPriceDebug`.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(77)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_ema_debug_events(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_ema_debug_events(enabled);
+
+            log::debug!("EmaDebugEventsSet( enabled: {:?} )", enabled);
+            Ok(())
+        }
+
+        /// Enables or disables recording every swap against a subnet pool in `SwapAudit`.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `enabled` - Whether swaps are recorded.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(78)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_swap_audit_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_swap_audit_enabled(enabled);
+
+            log::debug!("SwapAuditEnabledSet( enabled: {:?} )", enabled);
+            Ok(())
+        }
+
+        /// Sets the number of blocks for which swap audit records are retained.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `blocks` - The number of blocks to retain.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(79)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_swap_audit_retention(origin: OriginFor<T>, blocks: u64) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_swap_audit_retention(blocks);
+
+            log::debug!("SwapAuditRetentionSet( blocks: {:?} )", blocks);
+            Ok(())
+        }
+
+        /// Sets the distance in rao from a limit price within which the price of a limit order
+        /// still satisfies the limit.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `epsilon` - The epsilon in rao per alpha.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(80)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_limit_price_epsilon(origin: OriginFor<T>, epsilon: u64) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_limit_price_epsilon(epsilon);
+
+            log::debug!("LimitPriceEpsilonSet( epsilon: {:?} )", epsilon);
+            Ok(())
+        }
+
+        /// Sets the largest share of a trade's TAO value the staking fee may take before the
+        /// stake or unstake is rejected.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `max_fee_share_bps` - The maximum fee share in basis points, or zero for no limit.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(83)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_max_fee_share(
+            origin: OriginFor<T>,
+            max_fee_share_bps: u16,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_max_fee_share_bps(max_fee_share_bps);
+
+            log::debug!(
+                "MaxFeeShareSet( max_fee_share_bps: {:?} )",
+                max_fee_share_bps
+            );
+            Ok(())
+        }
+
+        /// Sets or clears the number of stakers a subnet needs before it accepts stakes above the
+        /// large stake threshold.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `min_stakers` - The minimum number of stakers, or `None` to accept large stakes.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(84)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_min_stakers_for_large_stake(
+            origin: OriginFor<T>,
+            netuid: u16,
+            min_stakers: Option<u32>,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_min_stakers_for_large_stake(netuid, min_stakers);
+
+            log::debug!(
+                "MinStakersForLargeStakeSet( netuid: {:?}, min_stakers: {:?} )",
+                netuid,
+                min_stakers
+            );
+            Ok(())
+        }
+
+        /// Sets the stake in TAO above which a stake counts as large.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `threshold` - The large stake threshold in rao.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(85)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_large_stake_threshold(
+            origin: OriginFor<T>,
+            threshold: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            pallet_subtensor::Pallet::<T>::set_large_stake_threshold(threshold);
+
+            log::debug!("LargeStakeThresholdSet( threshold: {:?} )", threshold);
+            Ok(())
+        }
+
+        /// Sets or clears the price ceiling of a subnet.
+        ///
+        /// While set, stakes that would push the subnet price above the ceiling are rejected.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the subnet owner or root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `ceiling` - The price ceiling in TAO per alpha, or `None` to remove it.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the subnet owner or root account.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(81)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_price_ceiling(
+            origin: OriginFor<T>,
+            netuid: u16,
+            ceiling: Option<U96F32>,
+        ) -> DispatchResult {
+            pallet_subtensor::Pallet::<T>::ensure_subnet_owner_or_root(origin, netuid)?;
+            pallet_subtensor::Pallet::<T>::set_price_ceiling(netuid, ceiling);
+
+            log::debug!(
+                "PriceCeilingSet( netuid: {:?}, ceiling: {:?} )",
+                netuid,
+                ceiling
+            );
+            Ok(())
+        }
+
+        /// Sets or clears the tao weight used for the stake weights of a subnet in place of the
+        /// global tao weight.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call, which must be the root account.
+        /// * `netuid` - The unique identifier for the subnet.
+        /// * `tao_weight` - The raw tao weight, or `None` to use the global tao weight.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - If the caller is not the root account.
+        /// * `SubnetDoesNotExist` - If the subnet does not exist.
+        ///
+        /// # Weight
+        /// Weight is handled by the `#[pallet::weight]` attribute.
+        #[pallet::call_index(82)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_subnet_tao_weight(
+            origin: OriginFor<T>,
+            netuid: u16,
+            tao_weight: Option<u64>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            pallet_subtensor::Pallet::<T>::set_subnet_tao_weight(netuid, tao_weight);
+
+            log::debug!(
+                "SubnetTaoWeightSet( netuid: {:?}, tao_weight: {:?} )",
+                netuid,
+                tao_weight
+            );
+            Ok(())
+        }
     }
 }
 
@@ -10,7 +10,7 @@ use pallet_subtensor::{Error as SubtensorError, SubnetOwner, Tempo, WeightsVersi
 use pallet_subtensor::Event;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_core::{Pair, U256, ed25519};
-use substrate_fixed::types::I96F32;
+use substrate_fixed::types::{I96F32, U96F32};
 
 use crate::Error;
 use crate::pallet::PrecompileEnable;
@@ -1711,3 +1711,39 @@ fn test_sudo_set_ema_halving() {
         assert_eq!(value_after_2, to_be_set);
     });
 }
+
+#[test]
+fn test_sudo_set_unstake_fee_multiplier() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, 10);
+        let owner = U256::from(10);
+        SubnetOwner::<Test>::insert(netuid, owner);
+
+        assert_eq!(
+            AdminUtils::sudo_set_unstake_fee_multiplier(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                U96F32::from_num(2)
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_unstake_fee_multiplier(
+                <<Test as Config>::RuntimeOrigin>::signed(owner),
+                netuid,
+                U96F32::from_num(pallet_subtensor::MAX_UNSTAKE_FEE_MULTIPLIER + 1)
+            ),
+            Err(SubtensorError::<Test>::InvalidUnstakeFeeMultiplier.into())
+        );
+        assert_ok!(AdminUtils::sudo_set_unstake_fee_multiplier(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            netuid,
+            U96F32::from_num(pallet_subtensor::MAX_UNSTAKE_FEE_MULTIPLIER)
+        ));
+        assert_eq!(
+            pallet_subtensor::UnstakeFeeMultiplier::<Test>::get(netuid),
+            U96F32::from_num(pallet_subtensor::MAX_UNSTAKE_FEE_MULTIPLIER)
+        );
+    });
+}
@@ -8,7 +8,7 @@ use pallet_subtensor::rpc_info::{
     metagraph::{Metagraph, SelectiveMetagraph},
     neuron_info::{NeuronInfo, NeuronInfoLite},
     show_subnet::SubnetState,
-    stake_info::StakeInfo,
+    stake_info::{FeeSchedule, StakeInfo},
     subnet_info::{SubnetHyperparams, SubnetInfo, SubnetInfov2},
 };
 use sp_runtime::AccountId32;
@@ -43,11 +43,14 @@ sp_api::decl_runtime_apis! {
         fn get_selective_metagraph(netuid: u16, metagraph_indexes: Vec<u16>) -> Option<SelectiveMetagraph<AccountId32>>;
     }
 
+    #[api_version(2)]
     pub trait StakeInfoRuntimeApi {
         fn get_stake_info_for_coldkey( coldkey_account: AccountId32 ) -> Vec<StakeInfo<AccountId32>>;
         fn get_stake_info_for_coldkeys( coldkey_accounts: Vec<AccountId32> ) -> Vec<(AccountId32, Vec<StakeInfo<AccountId32>>)>;
         fn get_stake_info_for_hotkey_coldkey_netuid( hotkey_account: AccountId32, coldkey_account: AccountId32, netuid: u16 ) -> Option<StakeInfo<AccountId32>>;
         fn get_stake_fee( origin: Option<(AccountId32, u16)>, origin_coldkey_account: AccountId32, destination: Option<(AccountId32, u16)>, destination_coldkey_account: AccountId32, amount: u64 ) -> u64;
+        #[api_version(2)]
+        fn get_fee_schedule( netuid: u16 ) -> FeeSchedule;
     }
 
     pub trait SubnetRegistrationRuntimeApi {
@@ -1,11 +1,14 @@
 use super::*;
 use frame_support::storage::IterableStorageMap;
+use frame_support::weights::Weight;
 use safe_math::*;
 use substrate_fixed::types::{U96F32, U110F18};
 
 impl<T: Config + pallet_drand::Config> Pallet<T> {
     /// Executes the necessary operations for each block.
-    pub fn block_step() -> Result<(), &'static str> {
+    ///
+    /// Returns the weight of the steps that are metered on top of the base block step weight.
+    pub fn block_step() -> Result<Weight, &'static str> {
         let block_number: u64 = Self::get_current_block_as_u64();
         log::debug!("block_step for block: {:?} ", block_number);
         // --- 1. Adjust difficulties.
@@ -15,11 +18,13 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
             U96F32::saturating_from_num(Self::get_block_emission().unwrap_or(0));
         log::debug!("Block emission: {:?}", block_emission);
         // --- 3. Run emission through network.
-        Self::run_coinbase(block_emission);
+        let mut weight = Self::run_coinbase(block_emission);
         // --- 4. Set pending children on the epoch; but only after the coinbase has been run.
         Self::try_set_pending_children(block_number);
+        // --- 5. Prune the swap audit records that expired.
+        weight.saturating_accrue(Self::prune_swap_audit(block_number));
         // Return ok.
-        Ok(())
+        Ok(weight)
     }
 
     fn try_set_pending_children(block_number: u64) {
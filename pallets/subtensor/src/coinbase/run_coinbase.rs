@@ -1,5 +1,6 @@
 use super::*;
 use alloc::collections::BTreeMap;
+use frame_support::weights::Weight;
 use safe_math::*;
 use substrate_fixed::types::U96F32;
 use tle::stream_ciphers::AESGCMStreamCipherProvider;
@@ -32,7 +33,10 @@ macro_rules! tou64 {
 }
 
 impl<T: Config> Pallet<T> {
-    pub fn run_coinbase(block_emission: U96F32) {
+    pub fn run_coinbase(block_emission: U96F32) -> Weight {
+        // The weight of the steps metered on top of the base block step weight.
+        let mut weight = Weight::zero();
+
         // --- 0. Get current block.
         let current_block: u64 = Self::get_current_block_as_u64();
         log::debug!("Current block: {:?}", current_block);
@@ -174,7 +178,8 @@ impl<T: Config> Pallet<T> {
             let alpha_issuance: U96F32 = asfloat!(Self::get_alpha_issuance(*netuid_i));
             log::debug!("alpha_issuance: {:?}", alpha_issuance);
             // Get tao_weight
-            let tao_weight: U96F32 = root_tao.saturating_mul(Self::get_tao_weight());
+            let tao_weight: U96F32 =
+                root_tao.saturating_mul(Self::get_tao_weight_for_subnet(*netuid_i));
             log::debug!("tao_weight: {:?}", tao_weight);
             // Get root proportional dividends.
             let root_proportion: U96F32 = tao_weight
@@ -209,10 +214,7 @@ impl<T: Config> Pallet<T> {
 
         // --- 7 Update moving prices after using them in the emission calculation.
         // Only update price EMA for subnets that we emit to.
-        for netuid_i in subnets_to_emit_to.iter() {
-            // Update moving prices after using them above.
-            Self::update_moving_price(*netuid_i);
-        }
+        weight.saturating_accrue(Self::update_all_moving_prices());
 
         // --- 7. Drain pending emission through the subnet based on tempo.
         // Run the epoch for *all* subnets, even if we don't emit anything.
@@ -255,11 +257,16 @@ impl<T: Config> Pallet<T> {
                     pending_swapped,
                     owner_cut,
                 );
+
+                // Snapshot the hotkey alpha at the end of the epoch.
+                weight.saturating_accrue(Self::snapshot_hotkey_alpha(netuid));
             } else {
                 // Increment
                 BlocksSinceLastStep::<T>::mutate(netuid, |total| *total = total.saturating_add(1));
             }
         }
+
+        weight
     }
 
     pub fn calculate_dividends_and_incentives(
@@ -562,7 +569,7 @@ impl<T: Config> Pallet<T> {
             owner_cut
         );
 
-        let tao_weight = Self::get_tao_weight();
+        let tao_weight = Self::get_tao_weight_for_subnet(netuid);
 
         // Run the epoch.
         let hotkey_emission: Vec<(T::AccountId, u64, u64)> =
@@ -622,7 +629,7 @@ impl<T: Config> Pallet<T> {
         }
 
         // Get TAO weight
-        let tao_weight: U96F32 = Self::get_tao_weight();
+        let tao_weight: U96F32 = Self::get_tao_weight_for_subnet(netuid);
 
         // Get the hotkey's stake including weight
         let root_stake: U96F32 = U96F32::saturating_from_num(Self::get_stake_for_hotkey_on_subnet(
@@ -689,7 +696,7 @@ impl<T: Config> Pallet<T> {
         let mut parent_contributions: Vec<(T::AccountId, U96F32)> = Vec::new();
 
         // Get the weights for root and alpha stakes in emission distribution
-        let tao_weight: U96F32 = Self::get_tao_weight();
+        let tao_weight: U96F32 = Self::get_tao_weight_for_subnet(netuid);
 
         // Get self contribution, removing any childkey proportions.
         let self_contribution = Self::get_self_contribution(hotkey, netuid);
@@ -57,6 +57,33 @@ extern crate alloc;
 
 pub const MAX_CRV3_COMMIT_SIZE_BYTES: u32 = 5000;
 
+/// Maximum number of entries returned by `get_top_stakers`.
+pub const MAX_TOP_STAKERS: u32 = 256;
+
+/// Number of rao in one TAO, also used as the rao denomination of one alpha.
+pub const RAO_PER_TAO: u64 = 1_000_000_000;
+
+/// Number of blocks covered by one stake flow counter.
+pub const STAKE_FLOW_INTERVAL: u64 = 100;
+
+/// Number of stake flow counters kept per subnet, bounding the window of `get_net_stake_flow`.
+pub const STAKE_FLOW_INTERVALS: u64 = 72;
+
+/// Maximum number of positions `reduce_all_positions` unstakes from in a single call.
+pub const MAX_REDUCE_POSITIONS: u32 = 64;
+
+/// Maximum multiplier a subnet may apply to the staking fee when removing stake.
+pub const MAX_UNSTAKE_FEE_MULTIPLIER: u64 = 5;
+
+/// Maximum number of expired epochs `snapshot_hotkey_alpha` prunes in a single call.
+pub const HOTKEY_ALPHA_SNAPSHOT_PRUNE_EPOCHS: u32 = 8;
+
+/// Maximum number of blocks `prune_swap_audit` walks over in a single call.
+pub const SWAP_AUDIT_PRUNE_BLOCKS: u64 = 64;
+
+/// Maximum number of swap audit records `prune_swap_audit` removes in a single call.
+pub const SWAP_AUDIT_PRUNE_RECORDS: u32 = 512;
+
 #[deny(missing_docs)]
 #[import_section(errors::errors)]
 #[import_section(events::events)]
@@ -82,7 +109,7 @@ pub mod pallet {
     use sp_std::collections::vec_deque::VecDeque;
     use sp_std::vec;
     use sp_std::vec::Vec;
-    use substrate_fixed::types::{I96F32, U64F64};
+    use substrate_fixed::types::{I96F32, U64F64, U96F32};
     use subtensor_macros::freeze_struct;
 
     #[cfg(not(feature = "std"))]
@@ -268,6 +295,30 @@ pub mod pallet {
         /// Additional information about the subnet
         pub additional: Vec<u8>,
     }
+
+    /// Data structure for the audit record of a swap against a subnet pool.
+    #[crate::freeze_struct("ea3b887382362c6")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct SwapRecord {
+        /// The subnet of the pool
+        pub netuid: u16,
+        /// Whether TAO was swapped for alpha
+        pub is_buy: bool,
+        /// The TAO reserve before the swap
+        pub tao_reserve_before: u64,
+        /// The alpha reserve before the swap
+        pub alpha_reserve_before: u64,
+        /// The TAO reserve after the swap
+        pub tao_reserve_after: u64,
+        /// The alpha reserve after the swap
+        pub alpha_reserve_after: u64,
+        /// The amount paid into the pool
+        pub amount_in: u64,
+        /// The amount received from the pool
+        pub amount_out: u64,
+        /// The staking fee charged
+        pub fee: u64,
+    }
     /// ============================
     /// ==== Staking + Accounts ====
     /// ============================
@@ -288,6 +339,11 @@ pub mod pallet {
         0
     }
     #[pallet::type_value]
+    /// Default value for zero.
+    pub fn DefaultZeroU32<T: Config>() -> u32 {
+        0
+    }
+    #[pallet::type_value]
     /// Default value for false.
     pub fn DefaultFalse<T: Config>() -> bool {
         false
@@ -794,6 +850,30 @@ pub mod pallet {
         I96F32::saturating_from_num(10_000_000)
     }
 
+    #[pallet::type_value]
+    /// Default multiplier applied to the staking fee when removing stake.
+    pub fn DefaultUnstakeFeeMultiplier<T: Config>() -> U96F32 {
+        U96F32::saturating_from_num(1)
+    }
+
+    #[pallet::type_value]
+    /// Default number of blocks for which swap audit records are retained.
+    pub fn DefaultSwapAuditRetention<T: Config>() -> u64 {
+        7200
+    }
+
+    #[pallet::type_value]
+    /// Default stake in TAO above which a stake counts as large (100 TAO).
+    pub fn DefaultLargeStakeThreshold<T: Config>() -> u64 {
+        100_000_000_000
+    }
+
+    #[pallet::type_value]
+    /// Default number of epochs for which hotkey alpha snapshots are retained, 0 disables them.
+    pub fn DefaultHotkeyAlphaSnapshotRetention<T: Config>() -> u32 {
+        0
+    }
+
     #[pallet::type_value]
     /// Default value for minimum activity cutoff
     pub fn DefaultMinActivityCutoff<T: Config>() -> u16 {
@@ -832,6 +912,9 @@ pub mod pallet {
     /// --- ITEM --> Global weight
     pub type TaoWeight<T> = StorageValue<_, u64, ValueQuery, DefaultTaoWeight<T>>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> Subnet weight overriding the global TaoWeight
+    pub type SubnetTaoWeight<T> = StorageMap<_, Identity, u16, u64, OptionQuery>;
+    #[pallet::storage]
     /// --- ITEM ( default_delegate_take )
     pub type MaxDelegateTake<T> = StorageValue<_, u16, ValueQuery, DefaultDelegateTake<T>>;
     #[pallet::storage]
@@ -972,6 +1055,8 @@ pub mod pallet {
     pub type DynamicBlock<T> = StorageValue<_, u64, ValueQuery>;
     #[pallet::storage] // --- ITEM ( moving_alpha ) -- subnet moving alpha.
     pub type SubnetMovingAlpha<T> = StorageValue<_, I96F32, ValueQuery, DefaultMovingAlpha<T>>;
+    #[pallet::storage] // --- ITEM ( enabled ) -- whether each moving price update emits a debug event.
+    pub type EmaDebugEvents<T> = StorageValue<_, bool, ValueQuery, DefaultFalse<T>>;
     #[pallet::storage] // --- MAP ( netuid ) --> moving_price | The subnet moving price.
     pub type SubnetMovingPrice<T: Config> =
         StorageMap<_, Identity, u16, I96F32, ValueQuery, DefaultMovingPrice<T>>;
@@ -1035,6 +1120,24 @@ pub mod pallet {
         ValueQuery,
         DefaultZeroU64<T>,
     >;
+    #[pallet::storage] // --- NMAP ( netuid, epoch, hot ) --> alpha | The total alpha a hotkey owned at the end of an epoch.
+    pub type HotkeyAlphaSnapshot<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, u16>,                  // subnet
+            NMapKey<Identity, u32>,                  // epoch
+            NMapKey<Blake2_128Concat, T::AccountId>, // hot
+        ),
+        u64, // Alpha
+        OptionQuery,
+    >;
+    #[pallet::storage] // --- MAP ( netuid ) --> epoch | The number of epochs snapshotted on a subnet.
+    pub type SubnetEpochIndex<T> = StorageMap<_, Identity, u16, u32, ValueQuery>;
+    #[pallet::storage] // --- MAP ( netuid ) --> epoch | The first epoch whose hotkey alpha snapshots have not been pruned yet.
+    pub type HotkeyAlphaSnapshotPrunedUntil<T> = StorageMap<_, Identity, u16, u32, ValueQuery>;
+    #[pallet::storage] // --- ITEM ( epochs ) | The number of epochs hotkey alpha snapshots are retained for.
+    pub type HotkeyAlphaSnapshotRetention<T> =
+        StorageValue<_, u32, ValueQuery, DefaultHotkeyAlphaSnapshotRetention<T>>;
     #[pallet::storage]
     /// DMAP ( hot, netuid ) --> total_alpha_shares | Returns the number of alpha shares for a hotkey on a subnet.
     pub type TotalHotkeyShares<T: Config> = StorageDoubleMap<
@@ -1058,6 +1161,23 @@ pub mod pallet {
         U64F64, // Shares
         ValueQuery,
     >;
+    #[pallet::storage] // --- DMAP ( hot, netuid ) --> Vec<cold> | Returns the coldkeys allowed to delegate to a hotkey on a subnet, if restricted.
+    pub type DelegationAllowlist<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        u16,
+        BoundedVec<T::AccountId, ConstU32<64>>,
+        OptionQuery,
+    >;
+    #[pallet::storage] // --- MAP ( cold ) --> slippage_bps | Returns the slippage tolerance a coldkey applies to stake operations without a limit price.
+    pub type DefaultSlippageBps<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u16, OptionQuery>;
+    #[pallet::storage] // --- ITEM ( epsilon ) | The distance in rao from a limit price within which a price still satisfies the limit.
+    pub type LimitPriceEpsilon<T> = StorageValue<_, u64, ValueQuery, DefaultZeroU64<T>>;
+    #[pallet::storage] // --- ITEM ( max_fee_share_bps ) | The largest share of a trade's TAO value the staking fee may take, or zero for no limit.
+    pub type MaxFeeShareBps<T> = StorageValue<_, u16, ValueQuery, DefaultZeroU16<T>>;
     #[pallet::storage] // --- MAP ( netuid ) --> token_symbol | Returns the token symbol for a subnet.
     pub type TokenSymbol<T: Config> =
         StorageMap<_, Identity, u16, Vec<u8>, ValueQuery, DefaultUnicodeVecU8<T>>;
@@ -1328,6 +1448,57 @@ pub mod pallet {
     pub type EMAPriceHalvingBlocks<T> =
         StorageMap<_, Identity, u16, u64, ValueQuery, DefaultEMAPriceMovingBlocks<T>>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> Block until which staking on the subnet is fee free.
+    pub type FeeHolidayUntil<T> = StorageMap<_, Identity, u16, u64, ValueQuery, DefaultZeroU64<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Whether the dividend based dynamic staking fee is enabled.
+    pub type DynamicFeeEnabled<T> = StorageMap<_, Identity, u16, bool, ValueQuery, DefaultTrue<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Minimum number of shares a stake must add to a hotkey's pool.
+    pub type MinShareIncrement<T> =
+        StorageMap<_, Identity, u16, U64F64, ValueQuery, DefaultSharePoolZero<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Share of the staking fee burned instead of added to reserves, in basis points.
+    pub type FeeBurnBps<T> = StorageMap<_, Identity, u16, u16, ValueQuery, DefaultZeroU16<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Multiplier applied to the staking fee when removing stake.
+    pub type UnstakeFeeMultiplier<T> =
+        StorageMap<_, Identity, u16, U96F32, ValueQuery, DefaultUnstakeFeeMultiplier<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Blocks a coldkey must wait between stakes to the same hotkey.
+    pub type StakeCooldownBlocks<T> =
+        StorageMap<_, Identity, u16, u32, ValueQuery, DefaultZeroU32<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Minimum pool liquidity overriding DefaultMinimumPoolLiquidity.
+    pub type MinPoolLiquidityOverride<T> = StorageMap<_, Identity, u16, u64, OptionQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Block at which the moving price was last updated.
+    pub type LastMovingPriceUpdateBlock<T> = StorageMap<_, Identity, u16, u64, OptionQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Whether limit orders on the subnet always allow partial fills.
+    pub type ForcePartialFills<T> = StorageMap<_, Identity, u16, bool, ValueQuery, DefaultFalse<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> Whether staking fees are redistributed to stakers instead of reserves.
+    pub type FeeRedistributionEnabled<T> =
+        StorageMap<_, Identity, u16, bool, ValueQuery, DefaultFalse<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> The price above which stakes into the subnet are rejected.
+    pub type PriceCeiling<T> = StorageMap<_, Identity, u16, U96F32, OptionQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> The number of distinct coldkeys holding alpha on the subnet.
+    pub type SubnetStakerCount<T> = StorageMap<_, Identity, u16, u32, ValueQuery>;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, coldkey ) --> The number of hotkeys the coldkey holds alpha of on the subnet.
+    pub type SubnetColdkeyPositions<T: Config> =
+        StorageDoubleMap<_, Identity, u16, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> The number of stakers the subnet needs before it accepts large stakes.
+    pub type MinStakersForLargeStake<T> = StorageMap<_, Identity, u16, u32, OptionQuery>;
+    #[pallet::storage]
+    /// --- ITEM ( large_stake_threshold ) | The stake in TAO above which a stake counts as large.
+    pub type LargeStakeThreshold<T> =
+        StorageValue<_, u64, ValueQuery, DefaultLargeStakeThreshold<T>>;
+    #[pallet::storage]
     /// --- MAP ( netuid ) --> global_RAO_recycled_for_registration
     pub type RAORecycledForRegistration<T> =
         StorageMap<_, Identity, u16, u64, ValueQuery, DefaultRAORecycledForRegistration<T>>;
@@ -1561,6 +1732,43 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    #[pallet::storage]
+    /// --- DMap (coldkey, hotkey) --> (netuid, tao, alpha, fee, slippage) | The result of the most recent stake.
+    pub type LastStakeResult<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        (u16, u64, u64, u64, U96F32),
+        OptionQuery,
+    >;
+    #[pallet::storage] // --- NMAP ( cold, hot, netuid ) --> fees | The total staking fees charged to a position, removed when the position is fully exited.
+    pub type PositionFeesPaid<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, T::AccountId>, // cold
+            NMapKey<Blake2_128Concat, T::AccountId>, // hot
+            NMapKey<Identity, u16>,                  // subnet
+        ),
+        u64, // Fees
+        ValueQuery,
+    >;
+    #[pallet::storage] // --- ITEM ( enabled ) | Whether swaps against subnet pools are recorded in `SwapAudit`.
+    pub type SwapAuditEnabled<T> = StorageValue<_, bool, ValueQuery, DefaultFalse<T>>;
+    #[pallet::storage] // --- ITEM ( blocks ) | The number of blocks swap audit records are retained for.
+    pub type SwapAuditRetention<T> = StorageValue<_, u64, ValueQuery, DefaultSwapAuditRetention<T>>;
+    #[pallet::storage] // --- DMAP ( block, index ) --> record | The audit record of a swap executed in a block.
+    pub type SwapAudit<T> =
+        StorageDoubleMap<_, Identity, u64, Identity, u32, SwapRecord, OptionQuery>;
+    #[pallet::storage] // --- MAP ( block ) --> count | The number of swaps audited in a block.
+    pub type SwapAuditCount<T> = StorageMap<_, Identity, u64, u32, ValueQuery>;
+    #[pallet::storage] // --- ITEM ( block ) | The first block whose swap audit records have not been pruned yet.
+    pub type SwapAuditPrunedUntil<T> = StorageValue<_, u64, ValueQuery>;
+    #[pallet::storage] // --- DMAP ( netuid, slot ) --> (interval, flow) | The net TAO staked into a subnet during an interval, kept in a ring of STAKE_FLOW_INTERVALS slots.
+    pub type StakeFlow<T> =
+        StorageDoubleMap<_, Identity, u16, Identity, u64, (u64, i64), ValueQuery>;
+
     /// =============================
     /// ==== EVM related storage ====
     /// =============================
@@ -1961,6 +2169,10 @@ where
                     )
                     .into();
                 }
+                // Bound the amount by the coldkey's default slippage, if it has one
+                let max_amount =
+                    Pallet::<T>::get_default_max_amount(who, *netuid, *amount_staked, true);
+
                 // Fully validate the user input
                 Self::result_to_validity(
                     Pallet::<T>::validate_add_stake(
@@ -1968,7 +2180,7 @@ where
                         hotkey,
                         *netuid,
                         *amount_staked,
-                        *amount_staked,
+                        max_amount,
                         false,
                     ),
                     Self::get_priority_staking(who, hotkey, *amount_staked),
@@ -1989,7 +2201,8 @@ where
                 }
 
                 // Calcaulate the maximum amount that can be executed with price limit
-                let max_amount = Pallet::<T>::get_max_amount_add(*netuid, *limit_price);
+                let limit_price = Pallet::<T>::apply_limit_price_epsilon(*limit_price, true);
+                let max_amount = Pallet::<T>::get_max_amount_add(*netuid, limit_price);
 
                 // Fully validate the user input
                 Self::result_to_validity(
@@ -2009,6 +2222,10 @@ where
                 netuid,
                 amount_unstaked,
             }) => {
+                // Bound the amount by the coldkey's default slippage, if it has one
+                let max_amount =
+                    Pallet::<T>::get_default_max_amount(who, *netuid, *amount_unstaked, false);
+
                 // Fully validate the user input
                 Self::result_to_validity(
                     Pallet::<T>::validate_remove_stake(
@@ -2016,7 +2233,7 @@ where
                         hotkey,
                         *netuid,
                         *amount_unstaked,
-                        *amount_unstaked,
+                        max_amount,
                         false,
                     ),
                     Self::get_priority_staking(who, hotkey, *amount_unstaked),
@@ -2030,7 +2247,8 @@ where
                 allow_partial,
             }) => {
                 // Calcaulate the maximum amount that can be executed with price limit
-                let max_amount = Pallet::<T>::get_max_amount_remove(*netuid, *limit_price);
+                let limit_price = Pallet::<T>::apply_limit_price_epsilon(*limit_price, false);
+                let max_amount = Pallet::<T>::get_max_amount_remove(*netuid, limit_price);
 
                 // Fully validate the user input
                 Self::result_to_validity(
@@ -213,5 +213,11 @@ mod config {
         /// Block number after a new subnet accept the start call extrinsic.
         #[pallet::constant]
         type DurationOfStartCall: Get<u64>;
+        /// Maximum number of children a hotkey can have on a subnet.
+        #[pallet::constant]
+        type MaxChildren: Get<u32>;
+        /// Maximum number of parents a hotkey can have on a subnet.
+        #[pallet::constant]
+        type MaxParents: Get<u32>;
     }
 }
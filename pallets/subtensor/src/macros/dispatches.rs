@@ -12,7 +12,7 @@ mod dispatches {
     use sp_core::ecdsa::Signature;
     use sp_runtime::traits::Saturating;
 
-    use crate::MAX_CRV3_COMMIT_SIZE_BYTES;
+    use crate::{MAX_CRV3_COMMIT_SIZE_BYTES, MAX_REDUCE_POSITIONS};
     /// Dispatchable functions allow users to interact with the pallet and invoke state changes.
     /// These functions materialize as "extrinsics", which are often compared to transactions.
     /// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
@@ -1774,6 +1774,7 @@ mod dispatches {
                 amount_staked,
                 limit_price,
                 allow_partial,
+                false,
             )
         }
 
@@ -2023,5 +2024,376 @@ mod dispatches {
         ) -> DispatchResult {
             Self::do_burn_alpha(origin, hotkey, amount, netuid)
         }
+
+        /// Sets or clears the coldkeys allowed to delegate stake to a hotkey on a subnet.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey owning the hotkey)
+        /// * `hotkey` - The hotkey account
+        /// * `netuid` - The subnet ID
+        /// * `allowlist` - The allowed coldkeys, or `None` to allow anyone to delegate
+        ///
+        /// # Events
+        /// Emits a `DelegationAllowlistSet` event on success.
+        #[pallet::call_index(103)]
+        #[pallet::weight((
+            Weight::from_parts(2_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 1)),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_delegation_allowlist(
+            origin: T::RuntimeOrigin,
+            hotkey: T::AccountId,
+            netuid: u16,
+            allowlist: Option<BoundedVec<T::AccountId, ConstU32<64>>>,
+        ) -> DispatchResult {
+            Self::do_set_delegation_allowlist(origin, hotkey, netuid, allowlist)
+        }
+
+        /// Moves stake from a hotkey to another across subnets, capping the staking fee.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `origin_hotkey` - The hotkey account to move stake from
+        /// * `destination_hotkey` - The hotkey account to move stake to
+        /// * `origin_netuid` - The subnet ID to move stake from
+        /// * `destination_netuid` - The subnet ID to move stake to
+        /// * `alpha_amount` - The alpha stake amount to move
+        /// * `max_fee` - The maximum total fee (in TAO) the caller is willing to pay
+        ///
+        /// # Errors
+        /// Returns `FeeExceedsMaximum` if the computed fee is greater than `max_fee`.
+        ///
+        /// # Events
+        /// Emits a `StakeMoved` event on success.
+        #[pallet::call_index(104)]
+        #[pallet::weight((Weight::from_parts(3_000_000, 0).saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Operational, Pays::No))]
+        pub fn move_stake_with_max_fee(
+            origin: T::RuntimeOrigin,
+            origin_hotkey: T::AccountId,
+            destination_hotkey: T::AccountId,
+            origin_netuid: u16,
+            destination_netuid: u16,
+            alpha_amount: u64,
+            max_fee: u64,
+        ) -> DispatchResult {
+            Self::do_move_stake_with_max_fee(
+                origin,
+                origin_hotkey,
+                destination_hotkey,
+                origin_netuid,
+                destination_netuid,
+                alpha_amount,
+                max_fee,
+            )
+        }
+
+        /// Transfers stake to another coldkey, optionally across subnets, capping the staking fee.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the origin coldkey)
+        /// * `destination_coldkey` - The coldkey to which the stake is transferred
+        /// * `hotkey` - The hotkey associated with the stake
+        /// * `origin_netuid` - The subnet ID to move stake from
+        /// * `destination_netuid` - The subnet ID to move stake to
+        /// * `alpha_amount` - The amount of stake to transfer
+        /// * `max_fee` - The maximum total fee (in TAO) the caller is willing to pay
+        ///
+        /// # Errors
+        /// Returns `FeeExceedsMaximum` if the computed fee is greater than `max_fee`.
+        ///
+        /// # Events
+        /// Emits a `StakeTransferred` event on success.
+        #[pallet::call_index(105)]
+        #[pallet::weight((Weight::from_parts(3_000_000, 0).saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Operational, Pays::No))]
+        pub fn transfer_stake_with_max_fee(
+            origin: T::RuntimeOrigin,
+            destination_coldkey: T::AccountId,
+            hotkey: T::AccountId,
+            origin_netuid: u16,
+            destination_netuid: u16,
+            alpha_amount: u64,
+            max_fee: u64,
+        ) -> DispatchResult {
+            Self::do_transfer_stake_with_max_fee(
+                origin,
+                destination_coldkey,
+                hotkey,
+                origin_netuid,
+                destination_netuid,
+                alpha_amount,
+                max_fee,
+            )
+        }
+
+        /// Unstakes the same share of every position held by the caller's coldkey.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `reduce_bps` - The share of each position to unstake, in basis points
+        ///
+        /// # Errors
+        /// Returns `InvalidReduceBps` if `reduce_bps` is greater than 10000, and
+        /// `TooManyPositions` if the coldkey holds more than `MAX_REDUCE_POSITIONS` positions.
+        ///
+        /// # Events
+        /// Emits a `PositionsReduced` event with the total TAO recovered.
+        ///
+        /// # Weight
+        /// Charged for `MAX_REDUCE_POSITIONS` unstakes.
+        #[pallet::call_index(106)]
+        #[pallet::weight((Weight::from_parts(111_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(11))
+		.saturating_add(T::DbWeight::get().writes(7))
+		.saturating_mul(u64::from(MAX_REDUCE_POSITIONS))
+		.saturating_add(T::DbWeight::get().reads(1)), DispatchClass::Normal, Pays::Yes))]
+        pub fn reduce_all_positions(origin: OriginFor<T>, reduce_bps: u16) -> DispatchResult {
+            Self::do_reduce_all_positions(origin, reduce_bps)
+        }
+
+        /// Injects TAO and alpha directly into a subnet's pool reserves without changing the price.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the subnet owner)
+        /// * `netuid` - The subnet ID
+        /// * `tao` - The amount of TAO to add to the pool
+        /// * `alpha` - The amount of alpha to add to the pool, taken from the owner's stake on the
+        ///   subnet owner hotkey
+        ///
+        /// # Errors
+        /// Returns `LiquidityInjectionChangesPrice` if the amounts are not in the ratio of the
+        /// current reserves, and `NotEnoughStakeToWithdraw` if the owner does not hold the alpha.
+        ///
+        /// # Events
+        /// Emits a `LiquidityInjected` event on success.
+        #[pallet::call_index(107)]
+        #[pallet::weight((Weight::from_parts(30_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(10))
+		.saturating_add(T::DbWeight::get().writes(7)), DispatchClass::Normal, Pays::Yes))]
+        pub fn inject_liquidity(
+            origin: OriginFor<T>,
+            netuid: u16,
+            tao: u64,
+            alpha: u64,
+        ) -> DispatchResult {
+            Self::do_inject_liquidity(origin, netuid, tao, alpha)
+        }
+
+        /// Adds stake to a hotkey on a subnet with a price limit, choosing whether the limit
+        /// applies to the swap price or to the price net of the staking fee.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `hotkey` - The hotkey to stake to
+        /// * `netuid` - The subnet ID
+        /// * `amount_staked` - The amount of TAO to stake
+        /// * `limit_price` - The limit price expressed in units of RAO per one Alpha
+        /// * `allow_partial` - Allows partial execution of the amount
+        /// * `limit_includes_fee` - If true, the limit is checked against the TAO paid per alpha
+        ///   received, including the fee
+        ///
+        /// # Errors
+        /// Returns `SlippageTooHigh` if the order cannot fill within the limit and partial
+        /// execution is not allowed.
+        ///
+        /// # Events
+        /// Emits a `StakeAdded` event on success.
+        #[pallet::call_index(108)]
+        #[pallet::weight((Weight::from_parts(124_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(10))
+		.saturating_add(T::DbWeight::get().writes(7)), DispatchClass::Normal, Pays::No))]
+        pub fn add_stake_limit_with_fee_option(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            netuid: u16,
+            amount_staked: u64,
+            limit_price: u64,
+            allow_partial: bool,
+            limit_includes_fee: bool,
+        ) -> DispatchResult {
+            Self::do_add_stake_limit(
+                origin,
+                hotkey,
+                netuid,
+                amount_staked,
+                limit_price,
+                allow_partial,
+                limit_includes_fee,
+            )
+        }
+
+        /// Moves stake between hotkeys and subnets, aborting if the destination would receive
+        /// less than a minimum amount of alpha.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `origin_hotkey` - The hotkey to move stake from
+        /// * `destination_hotkey` - The hotkey to move stake to
+        /// * `origin_netuid` - The subnet ID to move stake from
+        /// * `destination_netuid` - The subnet ID to move stake to
+        /// * `alpha_amount` - The amount of stake to move
+        /// * `min_dest_alpha` - The minimum alpha to receive on the destination subnet
+        ///
+        /// # Errors
+        /// Returns `DestinationAlphaTooLow` if less than `min_dest_alpha` would be staked.
+        ///
+        /// # Events
+        /// Emits a `StakeMoved` event on success.
+        #[pallet::call_index(109)]
+        #[pallet::weight((Weight::from_parts(3_000_000, 0).saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Operational, Pays::No))]
+        pub fn move_stake_with_min_dest_alpha(
+            origin: T::RuntimeOrigin,
+            origin_hotkey: T::AccountId,
+            destination_hotkey: T::AccountId,
+            origin_netuid: u16,
+            destination_netuid: u16,
+            alpha_amount: u64,
+            min_dest_alpha: u64,
+        ) -> DispatchResult {
+            Self::do_move_stake_with_min_dest_alpha(
+                origin,
+                origin_hotkey,
+                destination_hotkey,
+                origin_netuid,
+                destination_netuid,
+                alpha_amount,
+                min_dest_alpha,
+            )
+        }
+
+        /// Removes the caller's full stake on a hotkey on one subnet, leaving no dust.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `hotkey` - The hotkey to unstake from
+        /// * `netuid` - The subnet ID
+        ///
+        /// # Errors
+        /// Returns the same errors as `remove_stake`.
+        ///
+        /// # Events
+        /// Emits a `StakeRemoved` event on success.
+        #[pallet::call_index(110)]
+        #[pallet::weight((Weight::from_parts(111_000_000, 0)
+		.saturating_add(Weight::from_parts(0, 43991))
+		.saturating_add(T::DbWeight::get().reads(10))
+		.saturating_add(T::DbWeight::get().writes(7)), DispatchClass::Normal, Pays::No))]
+        pub fn remove_stake_all_for_subnet(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            netuid: u16,
+        ) -> DispatchResult {
+            Self::do_remove_stake_all_for_subnet(origin, hotkey, netuid)
+        }
+
+        /// Sets or clears the slippage tolerance applied to the caller's stake operations that
+        /// do not specify a limit price.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `slippage_bps` - The tolerance in basis points, or `None` to remove it
+        ///
+        /// # Errors
+        /// Returns `InvalidSlippageTolerance` if the tolerance is more than 10000 basis points.
+        ///
+        /// # Events
+        /// Emits a `DefaultSlippageSet` event on success.
+        #[pallet::call_index(111)]
+        #[pallet::weight((
+            Weight::from_parts(2_000_000, 0).saturating_add(T::DbWeight::get().writes(1)),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_default_slippage(
+            origin: T::RuntimeOrigin,
+            slippage_bps: Option<u16>,
+        ) -> DispatchResult {
+            Self::do_set_default_slippage(origin, slippage_bps)
+        }
+
+        /// Adds stake to several hotkeys in one call, skipping the hotkeys that are not
+        /// registered instead of failing the batch.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `stakes` - The hotkey, subnet and TAO amount of each stake
+        ///
+        /// # Errors
+        /// Returns the same errors as `add_stake` for the stakes whose hotkey exists.
+        ///
+        /// # Events
+        /// Emits a `StakeAdded` event for each stake added and a `StakeSkippedMissingHotkey`
+        /// event for each stake skipped.
+        #[pallet::call_index(112)]
+        #[pallet::weight((Weight::from_parts(124_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(10))
+		.saturating_add(T::DbWeight::get().writes(7))
+		.saturating_mul(stakes.len() as u64), DispatchClass::Normal, Pays::No))]
+        pub fn add_stake_multiple_skip_missing(
+            origin: OriginFor<T>,
+            stakes: BoundedVec<(T::AccountId, u16, u64), ConstU32<64>>,
+        ) -> DispatchResult {
+            Self::do_add_stake_multiple_skip_missing(origin, stakes)
+        }
+
+        /// Moves stake between two hotkeys on the same subnet without swapping through the
+        /// pool, paying only `DefaultStakingFee`.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `from_hotkey` - The hotkey to move the stake from
+        /// * `to_hotkey` - The hotkey to move the stake to
+        /// * `netuid` - The subnet ID
+        /// * `alpha_amount` - The alpha stake amount to move
+        ///
+        /// # Errors
+        /// Returns the same errors as `move_stake`, `DelegationNotAllowed` if the coldkey may not
+        /// delegate to `to_hotkey`, and `FeeExceedsUnstakeAmount` if the fee would consume the
+        /// whole amount.
+        ///
+        /// # Events
+        /// Emits a `HotkeyStakeSwapped` event on success.
+        #[pallet::call_index(113)]
+        #[pallet::weight((Weight::from_parts(3_000_000, 0).saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Operational, Pays::No))]
+        pub fn swap_hotkey_stake(
+            origin: T::RuntimeOrigin,
+            from_hotkey: T::AccountId,
+            to_hotkey: T::AccountId,
+            netuid: u16,
+            alpha_amount: u64,
+        ) -> DispatchResult {
+            Self::do_swap_hotkey_stake(origin, from_hotkey, to_hotkey, netuid, alpha_amount)
+        }
+
+        /// Removes the stake worth `tao_target` TAO, before fees, from a hotkey on a subnet.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the call (must be signed by the coldkey)
+        /// * `hotkey` - The hotkey to unstake from
+        /// * `netuid` - The subnet ID
+        /// * `tao_target` - The TAO value of the stake to remove
+        /// * `allow_partial` - Whether to unstake everything if the stake is worth less
+        ///
+        /// # Errors
+        /// Returns the same errors as `remove_stake`, `InsufficientLiquidity` if the pool cannot
+        /// pay out `tao_target`, and `NotEnoughStakeToWithdraw` if the stake is worth less than
+        /// `tao_target` and partial execution is not allowed.
+        ///
+        /// # Events
+        /// Emits a `StakeRemoved` event on success.
+        #[pallet::call_index(114)]
+        #[pallet::weight((Weight::from_parts(111_000_000, 0)
+		.saturating_add(Weight::from_parts(0, 43991))
+		.saturating_add(T::DbWeight::get().reads(11))
+		.saturating_add(T::DbWeight::get().writes(7)), DispatchClass::Normal, Pays::No))]
+        pub fn remove_stake_tao(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            netuid: u16,
+            tao_target: u64,
+            allow_partial: bool,
+        ) -> DispatchResult {
+            Self::do_remove_stake_tao(origin, hotkey, netuid, tao_target, allow_partial)
+        }
     }
 }
@@ -207,5 +207,43 @@ mod errors {
         UnableToRecoverPublicKey,
         /// Recovered public key is invalid.
         InvalidRecoveredPublicKey,
+        /// The coldkey is not on the hotkey's delegation allowlist.
+        DelegationNotAllowed,
+        /// The staking fee exceeds the maximum fee the caller is willing to pay.
+        FeeExceedsMaximum,
+        /// A child would exceed the maximum number of parents.
+        TooManyParents,
+        /// The staking fee would consume all of the TAO received for unstaking.
+        FeeExceedsUnstakeAmount,
+        /// The fee burn share must not exceed 10000 basis points.
+        InvalidFeeBurnBps,
+        /// The destination subnet of a stake transition does not exist.
+        DestinationSubnetNotExists,
+        /// The stake cooldown since the last stake to this hotkey has not elapsed.
+        StakeCooldownNotElapsed,
+        /// The share of positions to reduce must not exceed 10000 basis points.
+        InvalidReduceBps,
+        /// The injected liquidity would move the alpha price beyond the allowed tolerance.
+        LiquidityInjectionChangesPrice,
+        /// Less alpha would be staked on the destination subnet than the caller's minimum.
+        DestinationAlphaTooLow,
+        /// A hotkey cannot be its own child or parent.
+        SelfReferentialDelegation,
+        /// The slippage tolerance is more than 10000 basis points.
+        InvalidSlippageTolerance,
+        /// The stake would push the subnet price above its price ceiling.
+        PriceCeilingExceeded,
+        /// The staking fee exceeds the maximum share of the trade value.
+        FeeDisproportionate,
+        /// The subnet does not have enough stakers yet to accept a large stake.
+        NotEnoughStakersForLargeStake,
+        /// The unstake fee multiplier must not exceed `MAX_UNSTAKE_FEE_MULTIPLIER`.
+        InvalidUnstakeFeeMultiplier,
+        /// The coldkey holds more positions than a single call may reduce.
+        TooManyPositions,
+        /// The stake would mint fewer shares than the subnet's minimum share increment.
+        ShareIncrementTooLow,
+        /// The alpha swapped for the stake could not be credited to the hotkey.
+        StakeNotCredited,
     }
 }
@@ -323,5 +323,164 @@ mod events {
         /// - **netuid**: The network identifier.
         /// - **Enabled**: Is Commit-Reveal enabled.
         CommitRevealEnabled(u16, bool),
+
+        /// The delegation allowlist of a hotkey has been set or cleared.
+        ///
+        /// Parameters:
+        /// (hotkey, netuid)
+        DelegationAllowlistSet(T::AccountId, u16),
+
+        /// The fee holiday of a subnet has been set.
+        ///
+        /// Parameters:
+        /// (netuid, fee_holiday_until_block)
+        FeeHolidaySet(u16, u64),
+
+        /// The total alpha of a hotkey on a subnet has dropped to zero.
+        ///
+        /// Parameters:
+        /// (hotkey, netuid)
+        HotkeyExitedSubnet(T::AccountId, u16),
+
+        /// The dynamic staking fee has been enabled or disabled for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, enabled)
+        DynamicFeeEnabledSet(u16, bool),
+        /// The minimum share increment has been set for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, min_share_increment)
+        MinShareIncrementSet(u16, U64F64),
+        /// A staking fee has been credited to the TAO reserves of a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, amount)
+        FeeCreditedToReserves(u16, u64),
+        /// The number of epochs hotkey alpha snapshots are retained for has been set.
+        ///
+        /// Parameters:
+        /// (epochs)
+        HotkeyAlphaSnapshotRetentionSet(u32),
+        /// The share of the staking fee burned on a subnet has been set.
+        ///
+        /// Parameters:
+        /// (netuid, burn_bps)
+        FeeBurnBpsSet(u16, u16),
+        /// Part of a staking fee has been burned.
+        ///
+        /// Parameters:
+        /// (netuid, amount)
+        StakingFeeBurned(u16, u64),
+        /// The unstake fee multiplier has been set for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, multiplier)
+        UnstakeFeeMultiplierSet(u16, U96F32),
+        /// The stake cooldown has been set for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, cooldown_blocks)
+        StakeCooldownBlocksSet(u16, u32),
+        /// All positions of a coldkey have been reduced by the same share.
+        ///
+        /// Parameters:
+        /// (coldkey, reduce_bps, total_tao_unstaked)
+        PositionsReduced(T::AccountId, u16, u64),
+        /// The minimum pool liquidity override has been set or cleared for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, min_liquidity)
+        MinPoolLiquidityOverrideSet(u16, Option<u64>),
+        /// The subnet owner has injected liquidity into the pool reserves.
+        ///
+        /// Parameters:
+        /// (coldkey, netuid, tao, alpha)
+        LiquidityInjected(T::AccountId, u16, u64, u64),
+        /// The reserves of a subnet pool have changed after a swap.
+        ///
+        /// Parameters:
+        /// (netuid, new_tao, new_alpha_in, new_alpha_out)
+        ReservesChanged(u16, u64, u64, u64),
+        /// Forced partial fills of limit orders have been enabled or disabled for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, force)
+        ForcePartialFillsSet(u16, bool),
+        /// Fee redistribution to stakers has been enabled or disabled for a subnet.
+        ///
+        /// Parameters:
+        /// (netuid, enabled)
+        FeeRedistributionEnabledSet(u16, bool),
+        /// A staking fee has been swapped for alpha and distributed to the stakers of a hotkey.
+        ///
+        /// Parameters:
+        /// (hotkey, netuid, alpha)
+        FeeRedistributedToStakers(T::AccountId, u16, u64),
+        /// Debug events for moving price updates have been enabled or disabled.
+        ///
+        /// Parameters:
+        /// (enabled)
+        EmaDebugEventsSet(bool),
+        /// The intermediate values of a moving price update, emitted when `EmaDebugEvents` is set.
+        ///
+        /// Parameters:
+        /// (netuid, blocks_since_start_call, alpha, spot_price, new_moving_price)
+        MovingPriceDebug(u16, u64, U96F32, U96F32, I96F32),
+        /// The default slippage tolerance of a coldkey has been set or cleared.
+        ///
+        /// Parameters:
+        /// (coldkey, slippage_bps)
+        DefaultSlippageSet(T::AccountId, Option<u16>),
+        /// Swap auditing has been enabled or disabled.
+        ///
+        /// Parameters:
+        /// (enabled)
+        SwapAuditEnabledSet(bool),
+        /// The number of blocks swap audit records are retained for has been set.
+        ///
+        /// Parameters:
+        /// (blocks)
+        SwapAuditRetentionSet(u64),
+        /// A stake of a batch was skipped because its hotkey does not exist.
+        ///
+        /// Parameters:
+        /// (coldkey, hotkey, netuid, amount)
+        StakeSkippedMissingHotkey(T::AccountId, T::AccountId, u16, u64),
+        /// Stake has been moved between two hotkeys on a subnet without a swap.
+        ///
+        /// Parameters:
+        /// (coldkey, from_hotkey, to_hotkey, netuid, alpha_moved, fee_alpha)
+        HotkeyStakeSwapped(T::AccountId, T::AccountId, T::AccountId, u16, u64, u64),
+        /// The limit price epsilon has been set.
+        ///
+        /// Parameters:
+        /// (epsilon)
+        LimitPriceEpsilonSet(u64),
+        /// The price ceiling of a subnet has been set or cleared.
+        ///
+        /// Parameters:
+        /// (netuid, ceiling)
+        PriceCeilingSet(u16, Option<U96F32>),
+        /// The tao weight override of a subnet has been set or cleared.
+        ///
+        /// Parameters:
+        /// (netuid, tao_weight)
+        SubnetTaoWeightSet(u16, Option<u64>),
+        /// The maximum share of a trade's value the staking fee may take has been set.
+        ///
+        /// Parameters:
+        /// (max_fee_share_bps)
+        MaxFeeShareSet(u16),
+        /// The minimum number of stakers before a subnet accepts large stakes has been set.
+        ///
+        /// Parameters:
+        /// (netuid, min_stakers)
+        MinStakersForLargeStakeSet(u16, Option<u32>),
+        /// The stake above which a stake counts as large has been set.
+        ///
+        /// Parameters:
+        /// (threshold)
+        LargeStakeThresholdSet(u64),
     }
 }
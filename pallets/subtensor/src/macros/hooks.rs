@@ -17,12 +17,13 @@ mod hooks {
         fn on_initialize(_block_number: BlockNumberFor<T>) -> Weight {
             let block_step_result = Self::block_step();
             match block_step_result {
-                Ok(_) => {
+                Ok(weight) => {
                     // --- If the block step was successful, return the weight.
                     log::debug!("Successfully ran block step.");
                     Weight::from_parts(110_634_229_000_u64, 0)
                         .saturating_add(T::DbWeight::get().reads(8304_u64))
                         .saturating_add(T::DbWeight::get().writes(110_u64))
+                        .saturating_add(weight)
                 }
                 Err(e) => {
                     // --- If the block step was unsuccessful, return the weight anyway.
@@ -87,7 +88,11 @@ mod hooks {
                 // Remove all zero value entries in TotalHotkeyAlpha
                 .saturating_add(migrations::migrate_remove_zero_total_hotkey_alpha::migrate_remove_zero_total_hotkey_alpha::<T>())
                 // Wipe existing items to prevent bad decoding for new type
-                .saturating_add(migrations::migrate_upgrade_revealed_commitments::migrate_upgrade_revealed_commitments::<T>());
+                .saturating_add(migrations::migrate_upgrade_revealed_commitments::migrate_upgrade_revealed_commitments::<T>())
+                // Seed the staker count of every subnet from the existing alpha positions
+                .saturating_add(migrations::migrate_seed_subnet_staker_count::migrate_seed_subnet_staker_count::<T>())
+                // Merge the cached stake slippage into LastStakeResult
+                .saturating_add(migrations::migrate_last_stake_result_slippage::migrate_last_stake_result_slippage::<T>());
             weight
         }
 
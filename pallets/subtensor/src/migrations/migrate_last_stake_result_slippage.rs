@@ -0,0 +1,74 @@
+use super::*;
+use frame_support::{pallet_prelude::*, storage_alias, traits::Get, weights::Weight};
+use log;
+use scale_info::prelude::string::String;
+use substrate_fixed::types::U96F32;
+
+/// Module containing the deprecated slippage cache merged into `LastStakeResult`
+pub mod deprecated_last_stake_slippage_format {
+    use super::*;
+
+    #[storage_alias]
+    pub(super) type LastStakeSlippage<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        U96F32,
+        OptionQuery,
+    >;
+}
+
+/// Extends every `LastStakeResult` entry with the realized slippage previously cached in
+/// `LastStakeSlippage`, then removes that map. Entries without a cached slippage get zero.
+pub fn migrate_last_stake_result_slippage<T: Config>() -> Weight {
+    let migration_name = b"migrate_last_stake_result_slippage".to_vec();
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            migration_name
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    // Move the cached slippage into the stake results.
+    let mut migrated = 0u64;
+    LastStakeResult::<T>::translate::<(u16, u64, u64, u64), _>(
+        |coldkey, hotkey, (netuid, tao, alpha, fee)| {
+            migrated = migrated.saturating_add(1);
+            let slippage = deprecated_last_stake_slippage_format::LastStakeSlippage::<T>::take(
+                &coldkey, &hotkey,
+            )
+            .unwrap_or(U96F32::saturating_from_num(0));
+            Some((netuid, tao, alpha, fee, slippage))
+        },
+    );
+    weight = weight.saturating_add(
+        T::DbWeight::get().reads_writes(migrated.saturating_mul(2), migrated.saturating_mul(2)),
+    );
+
+    // Remove any slippage left without a stake result.
+    let removed =
+        deprecated_last_stake_slippage_format::LastStakeSlippage::<T>::clear(u32::MAX, None).unique;
+    weight = weight.saturating_add(T::DbWeight::get().writes(removed as u64));
+
+    log::info!("Migrated {} entries in LastStakeResult", migrated);
+
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{:?}' completed successfully.",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    weight
+}
@@ -0,0 +1,79 @@
+use super::*;
+use alloc::collections::BTreeMap;
+use frame_support::{traits::Get, weights::Weight};
+use log;
+use scale_info::prelude::string::String;
+use substrate_fixed::types::U64F64;
+
+pub fn migrate_seed_subnet_staker_count<T: Config>() -> Weight {
+    let migration_name = b"migrate_seed_subnet_staker_count".to_vec();
+    let mut weight = T::DbWeight::get().reads(1);
+
+    // ------------------------------
+    // Step 0: Check if already run
+    // ------------------------------
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            migration_name
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    // ------------------------------
+    // Step 1: Count the positions of each coldkey and the distinct coldkeys on each subnet
+    // ------------------------------
+
+    let _ = SubnetColdkeyPositions::<T>::clear(u32::MAX, None);
+    let mut counts: BTreeMap<u16, u32> = BTreeMap::new();
+    let mut positions_read = 0u64;
+    let mut positions_written = 0u64;
+
+    for ((_, coldkey, netuid), shares) in Alpha::<T>::iter() {
+        positions_read = positions_read.saturating_add(1);
+        if shares != U64F64::saturating_from_num(0) {
+            let positions = SubnetColdkeyPositions::<T>::get(netuid, &coldkey);
+            SubnetColdkeyPositions::<T>::insert(netuid, &coldkey, positions.saturating_add(1));
+            positions_written = positions_written.saturating_add(1);
+            if positions == 0 {
+                let count = counts.entry(netuid).or_insert(0);
+                *count = count.saturating_add(1);
+            }
+        }
+    }
+    weight = weight.saturating_add(T::DbWeight::get().reads_writes(
+        positions_read.saturating_add(positions_written),
+        positions_written.saturating_add(1),
+    ));
+
+    // ------------------------------
+    // Step 2: Overwrite SubnetStakerCount with the counted coldkeys
+    // ------------------------------
+
+    let _ = SubnetStakerCount::<T>::clear(u32::MAX, None);
+    for (netuid, count) in counts.iter() {
+        SubnetStakerCount::<T>::insert(netuid, count);
+    }
+    weight =
+        weight.saturating_add(T::DbWeight::get().writes((counts.len() as u64).saturating_add(1)));
+
+    log::info!("Seeded SubnetStakerCount for {} subnets.", counts.len());
+
+    // ------------------------------
+    // Step 3: Mark Migration as Completed
+    // ------------------------------
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{:?}' completed successfully.",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    weight
+}
@@ -18,6 +18,19 @@ pub struct StakeInfo<AccountId: TypeInfo + Encode + Decode> {
     is_registered: bool,
 }
 
+#[freeze_struct("6d01c0bfd35d81e2")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct FeeSchedule {
+    pub netuid: Compact<u16>,
+    pub default_fee: Compact<u64>,
+    pub dynamic_fee_enabled: bool,
+    pub dynamic_fee_floor_rate: U96F32,
+    pub fee_holiday_until: Compact<u64>,
+    pub fee_burn_bps: Compact<u16>,
+    pub unstake_fee_multiplier: U96F32,
+    pub fee_redistribution_enabled: bool,
+}
+
 impl<T: Config> Pallet<T> {
     fn _get_stake_info_for_coldkeys(
         coldkeys: Vec<T::AccountId>,
@@ -142,4 +155,18 @@ impl<T: Config> Pallet<T> {
             U96F32::saturating_from_num(amount),
         )
     }
+
+    /// Returns every parameter that determines the staking fee of a subnet.
+    pub fn get_fee_schedule(netuid: u16) -> FeeSchedule {
+        FeeSchedule {
+            netuid: netuid.into(),
+            default_fee: DefaultStakingFee::<T>::get().into(),
+            dynamic_fee_enabled: DynamicFeeEnabled::<T>::get(netuid),
+            dynamic_fee_floor_rate: Self::get_dynamic_fee_floor_rate(),
+            fee_holiday_until: FeeHolidayUntil::<T>::get(netuid).into(),
+            fee_burn_bps: FeeBurnBps::<T>::get(netuid).into(),
+            unstake_fee_multiplier: UnstakeFeeMultiplier::<T>::get(netuid),
+            fee_redistribution_enabled: FeeRedistributionEnabled::<T>::get(netuid),
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use super::*;
-use substrate_fixed::types::I96F32;
+use sp_core::U256;
+use substrate_fixed::types::{I96F32, U96F32};
 
 impl<T: Config> Pallet<T> {
     /// ---- The implementation for the extrinsic add_stake: Adds stake to a hotkey account.
@@ -47,30 +48,95 @@ impl<T: Config> Pallet<T> {
             stake_to_be_added
         );
 
-        // 2. Validate user input
+        // 2. Validate user input, bounded by the coldkey's default slippage if it has one
+        let max_amount = Self::get_default_max_amount(&coldkey, netuid, stake_to_be_added, true);
         Self::validate_add_stake(
             &coldkey,
             &hotkey,
             netuid,
             stake_to_be_added,
-            stake_to_be_added,
+            max_amount,
             false,
         )?;
 
-        // 3. Ensure the remove operation from the coldkey is a success.
+        // 3. Ensure the staking fee is in proportion to the stake.
+        let fee = Self::calculate_staking_fee(
+            None,
+            &coldkey,
+            Some((&hotkey, netuid)),
+            &coldkey,
+            U96F32::saturating_from_num(stake_to_be_added),
+        );
+        ensure!(
+            !Self::fee_is_disproportionate(fee, stake_to_be_added),
+            Error::<T>::FeeDisproportionate
+        );
+
+        // 4. Ensure the remove operation from the coldkey is a success.
         let tao_staked: I96F32 =
             Self::remove_balance_from_coldkey_account(&coldkey, stake_to_be_added)?.into();
 
-        // 4. Swap the stake into alpha on the subnet and increase counters.
+        // 5. Swap the stake into alpha on the subnet and increase counters.
         // Emit the staking event.
-        let fee = DefaultStakingFee::<T>::get();
         Self::stake_into_subnet(
             &hotkey,
             &coldkey,
             netuid,
             tao_staked.saturating_to_num::<u64>(),
             fee,
-        );
+        )?;
+
+        // Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic add_stake_multiple_skip_missing: Adds stake
+    /// to several hotkeys, skipping the hotkeys that are not registered.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'stakes' (BoundedVec<(T::AccountId, u16, u64), ConstU32<64>>):
+    ///     -  The hotkey, subnet and TAO amount of each stake.
+    ///
+    /// # Event:
+    /// * StakeAdded;
+    ///     -  For each stake that is added.
+    ///
+    /// * StakeSkippedMissingHotkey;
+    ///     -  For each stake whose hotkey does not exist.
+    ///
+    /// # Raises:
+    /// * Any error of `add_stake` for a stake whose hotkey exists. The whole batch is reverted.
+    ///
+    pub fn do_add_stake_multiple_skip_missing(
+        origin: T::RuntimeOrigin,
+        stakes: BoundedVec<(T::AccountId, u16, u64), ConstU32<64>>,
+    ) -> dispatch::DispatchResult {
+        // 1. We check that the transaction is signed by the caller.
+        let coldkey = ensure_signed(origin.clone())?;
+
+        // 2. Stake to each existing hotkey and report the missing ones.
+        for (hotkey, netuid, amount) in stakes {
+            if !Self::hotkey_account_exists(&hotkey) {
+                log::debug!(
+                    "StakeSkippedMissingHotkey( coldkey:{:?}, hotkey:{:?}, netuid:{:?}, amount:{:?} )",
+                    coldkey,
+                    hotkey,
+                    netuid,
+                    amount
+                );
+                Self::deposit_event(Event::StakeSkippedMissingHotkey(
+                    coldkey.clone(),
+                    hotkey,
+                    netuid,
+                    amount,
+                ));
+                continue;
+            }
+            Self::do_add_stake(origin.clone(), hotkey, netuid, amount)?;
+        }
 
         // Ok and return.
         Ok(())
@@ -96,6 +162,10 @@ impl<T: Config> Pallet<T> {
     ///     - Allows partial execution of the amount. If set to false, this becomes
     ///       fill or kill type or order.
     ///
+    ///  * 'limit_includes_fee' (bool):
+    ///     - If set to true, the limit price is checked against the price net of the staking
+    ///       fee (TAO paid per alpha received). Otherwise it is checked against the swap price.
+    ///
     /// # Event:
     /// * StakeAdded;
     ///     -  On the successfully adding stake to a global account.
@@ -120,6 +190,7 @@ impl<T: Config> Pallet<T> {
         stake_to_be_added: u64,
         limit_price: u64,
         allow_partial: bool,
+        limit_includes_fee: bool,
     ) -> dispatch::DispatchResult {
         // 1. We check that the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
         let coldkey = ensure_signed(origin)?;
@@ -131,8 +202,30 @@ impl<T: Config> Pallet<T> {
             stake_to_be_added
         );
 
+        // The subnet may force partial execution of all limit orders.
+        let allow_partial = allow_partial || ForcePartialFills::<T>::get(netuid);
+
         // 2. Calcaulate the maximum amount that can be executed with price limit
-        let max_amount = Self::get_max_amount_add(netuid, limit_price);
+        let limit_price = Self::apply_limit_price_epsilon(limit_price, true);
+        let max_amount = if limit_includes_fee {
+            let fee = Self::calculate_staking_fee(
+                None,
+                &coldkey,
+                Some((&hotkey, netuid)),
+                &coldkey,
+                U96F32::saturating_from_num(stake_to_be_added),
+            );
+            let (min_amount, max_amount) =
+                Self::get_amount_range_add_net_of_fee(netuid, limit_price, fee);
+            // Below the minimum the fee alone pushes the net price over the limit
+            if stake_to_be_added.min(max_amount) < min_amount {
+                0
+            } else {
+                max_amount
+            }
+        } else {
+            Self::get_max_amount_add(netuid, limit_price)
+        };
         let mut possible_stake = stake_to_be_added;
         if possible_stake > max_amount {
             possible_stake = max_amount;
@@ -153,20 +246,32 @@ impl<T: Config> Pallet<T> {
             Self::maybe_become_delegate(&hotkey);
         }
 
-        // 5. Ensure the remove operation from the coldkey is a success.
+        // 5. Ensure the staking fee is in proportion to the stake.
+        let fee = Self::calculate_staking_fee(
+            None,
+            &coldkey,
+            Some((&hotkey, netuid)),
+            &coldkey,
+            U96F32::saturating_from_num(possible_stake),
+        );
+        ensure!(
+            !Self::fee_is_disproportionate(fee, possible_stake),
+            Error::<T>::FeeDisproportionate
+        );
+
+        // 6. Ensure the remove operation from the coldkey is a success.
         let tao_staked: I96F32 =
             Self::remove_balance_from_coldkey_account(&coldkey, possible_stake)?.into();
 
-        // 6. Swap the stake into alpha on the subnet and increase counters.
+        // 7. Swap the stake into alpha on the subnet and increase counters.
         // Emit the staking event.
-        let fee = DefaultStakingFee::<T>::get();
         Self::stake_into_subnet(
             &hotkey,
             &coldkey,
             netuid,
             tao_staked.saturating_to_num::<u64>(),
             fee,
-        );
+        )?;
 
         // Ok and return.
         Ok(())
@@ -225,4 +330,74 @@ impl<T: Config> Pallet<T> {
             u64::MAX
         }
     }
+
+    // Returns the range of RAO amounts whose price net of `fee` (the RAO paid per alpha received)
+    // does not exceed the price limit, as (min, max). An empty range is returned as (0, 0).
+    pub fn get_amount_range_add_net_of_fee(netuid: u16, limit_price: u64, fee: u64) -> (u64, u64) {
+        // Without a fee the net price is the swap price
+        if fee == 0 {
+            return (0, Self::get_max_amount_add(netuid, limit_price));
+        }
+
+        let tao = U256::from(1_000_000_000_u64);
+        let limit_price = U256::from(limit_price);
+        let fee = U256::from(fee);
+        let to_u64 = |amount: U256| {
+            if amount > U256::from(u64::MAX) {
+                u64::MAX
+            } else {
+                amount.low_u64()
+            }
+        };
+
+        // Corner case: root and stao
+        // Alpha is received 1:1 for the amount less the fee, so the net price x / (x - fee)
+        // falls towards 1e9 rao as the amount x grows. x >= limit * fee / (limit - 1e9).
+        if (netuid == Self::get_root_netuid()) || (SubnetMechanism::<T>::get(netuid)) == 0 {
+            let Some(margin) = limit_price.checked_sub(tao).filter(|m| !m.is_zero()) else {
+                return (0, 0);
+            };
+            let min_amount = limit_price
+                .saturating_mul(fee)
+                .saturating_add(margin.saturating_sub(U256::one()))
+                .checked_div(margin)
+                .unwrap_or_default();
+            return (to_u64(min_amount), u64::MAX);
+        }
+
+        // Corner case: empty reserves. Staking can't happen.
+        let alpha_in = U256::from(SubnetAlphaIn::<T>::get(netuid));
+        let tao_reserve = U256::from(SubnetTAO::<T>::get(netuid));
+        if alpha_in.is_zero() || tao_reserve.is_zero() {
+            return (0, 0);
+        }
+
+        // Main case: the amount x pays x * (tao_reserve + x - fee) / (alpha_in * (x - fee)) per
+        // alpha. With m = limit_price * alpha_in / 1e9 the limit holds between the roots of
+        // x^2 - (m + fee - tao_reserve) * x + m * fee = 0.
+        let m = limit_price
+            .saturating_mul(alpha_in)
+            .checked_div(tao)
+            .unwrap_or_default();
+        let Some(b) = m.saturating_add(fee).checked_sub(tao_reserve) else {
+            return (0, 0);
+        };
+        let Some(discriminant) = b
+            .saturating_mul(b)
+            .checked_sub(m.saturating_mul(fee).saturating_mul(U256::from(4)))
+        else {
+            return (0, 0);
+        };
+        let root = discriminant.integer_sqrt();
+        let min_amount = b
+            .saturating_sub(root)
+            .saturating_add(U256::one())
+            .checked_div(U256::from(2))
+            .unwrap_or_default();
+        let max_amount = b
+            .saturating_add(root)
+            .checked_div(U256::from(2))
+            .unwrap_or_default();
+        (to_u64(min_amount), to_u64(max_amount))
+    }
 }
@@ -0,0 +1,78 @@
+use super::*;
+use safe_math::*;
+use substrate_fixed::types::U96F32;
+
+impl<T: Config> Pallet<T> {
+    /// ---- The implementation for the extrinsic set_default_slippage
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>::RuntimeOrigin):
+    ///     - The signature of the caller's coldkey.
+    ///
+    /// * 'slippage_bps' (Option<u16>):
+    ///     - The price movement the coldkey tolerates on stake operations without a limit price,
+    ///       in basis points. `None` removes the default.
+    ///
+    /// # Event:
+    /// * DefaultSlippageSet;
+    ///     - On successfully setting or clearing the default.
+    ///
+    /// # Raises:
+    /// * 'InvalidSlippageTolerance':
+    ///     - The tolerance is more than 10000 basis points.
+    ///
+    pub fn do_set_default_slippage(
+        origin: T::RuntimeOrigin,
+        slippage_bps: Option<u16>,
+    ) -> dispatch::DispatchResult {
+        // --- 1. We check the coldkey signature.
+        let coldkey = ensure_signed(origin)?;
+
+        // --- 2. Set or clear the default.
+        match slippage_bps {
+            Some(slippage_bps) => {
+                ensure!(slippage_bps <= 10_000, Error::<T>::InvalidSlippageTolerance);
+                DefaultSlippageBps::<T>::insert(&coldkey, slippage_bps);
+            }
+            None => DefaultSlippageBps::<T>::remove(&coldkey),
+        }
+
+        // --- 3. Emit the event.
+        Self::deposit_event(Event::DefaultSlippageSet(coldkey, slippage_bps));
+        Ok(())
+    }
+
+    /// Returns the maximum amount a coldkey can stake or unstake without a limit price.
+    ///
+    /// With a `DefaultSlippageBps` set, the limit price is the current price moved by the
+    /// tolerance against the caller, and the bound comes from `get_max_amount_add` or
+    /// `get_max_amount_remove`. Without one, the whole `amount` is allowed.
+    pub fn get_default_max_amount(
+        coldkey: &T::AccountId,
+        netuid: u16,
+        amount: u64,
+        is_buy: bool,
+    ) -> u64 {
+        let Some(slippage_bps) = DefaultSlippageBps::<T>::get(coldkey) else {
+            return amount;
+        };
+
+        let bps = U96F32::saturating_from_num(10_000);
+        let tolerance = U96F32::saturating_from_num(slippage_bps);
+        let price = Self::get_alpha_price(netuid)
+            .saturating_mul(U96F32::saturating_from_num(1_000_000_000));
+        if is_buy {
+            let limit_price = price
+                .saturating_mul(bps.saturating_add(tolerance))
+                .safe_div(bps)
+                .saturating_to_num::<u64>();
+            Self::get_max_amount_add(netuid, limit_price)
+        } else {
+            let limit_price = price
+                .saturating_mul(bps.saturating_sub(tolerance))
+                .safe_div(bps)
+                .saturating_to_num::<u64>();
+            Self::get_max_amount_remove(netuid, limit_price)
+        }
+    }
+}
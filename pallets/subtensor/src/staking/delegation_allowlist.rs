@@ -0,0 +1,79 @@
+use super::*;
+
+impl<T: Config> Pallet<T> {
+    /// ---- The implementation for the extrinsic set_delegation_allowlist
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>::RuntimeOrigin):
+    ///     - The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     - The hotkey whose delegators are restricted (must be owned by the coldkey.)
+    ///
+    /// * 'netuid' (u16):
+    ///     - The subnet the allowlist applies to.
+    ///
+    /// * 'allowlist' (Option<BoundedVec<T::AccountId, ConstU32<64>>>):
+    ///     - The coldkeys allowed to delegate. `None` removes the restriction.
+    ///
+    /// # Event:
+    /// * DelegationAllowlistSet;
+    ///     - On successfully setting or clearing the allowlist.
+    ///
+    /// # Raises:
+    /// * 'SubnetNotExists':
+    ///     - The subnet does not exist.
+    ///
+    /// * 'HotKeyAccountNotExists':
+    ///     - The hotkey is not registered on the network.
+    ///
+    /// * 'NonAssociatedColdKey':
+    ///     - The hotkey is not owned by the calling coldkey.
+    ///
+    pub fn do_set_delegation_allowlist(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        netuid: u16,
+        allowlist: Option<BoundedVec<T::AccountId, ConstU32<64>>>,
+    ) -> dispatch::DispatchResult {
+        // --- 1. We check the coldkey signature.
+        let coldkey = ensure_signed(origin)?;
+
+        // --- 2. Ensure the subnet exists and the coldkey owns the hotkey.
+        ensure!(Self::if_subnet_exist(netuid), Error::<T>::SubnetNotExists);
+        ensure!(
+            Self::hotkey_account_exists(&hotkey),
+            Error::<T>::HotKeyAccountNotExists
+        );
+        ensure!(
+            Self::coldkey_owns_hotkey(&coldkey, &hotkey),
+            Error::<T>::NonAssociatedColdKey
+        );
+
+        // --- 3. Set or clear the allowlist.
+        match allowlist {
+            Some(allowlist) => DelegationAllowlist::<T>::insert(&hotkey, netuid, allowlist),
+            None => DelegationAllowlist::<T>::remove(&hotkey, netuid),
+        }
+
+        // --- 4. Emit the event.
+        Self::deposit_event(Event::DelegationAllowlistSet(hotkey, netuid));
+        Ok(())
+    }
+
+    /// Returns true if the coldkey may delegate stake to the hotkey on the subnet.
+    ///
+    /// Anyone may delegate when no allowlist is set. The owner of the hotkey is always allowed.
+    pub fn is_delegation_allowed(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: u16,
+    ) -> bool {
+        match DelegationAllowlist::<T>::get(hotkey, netuid) {
+            Some(allowlist) => {
+                Self::coldkey_owns_hotkey(coldkey, hotkey) || allowlist.contains(coldkey)
+            }
+            None => true,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use alloc::collections::BTreeMap;
 use safe_math::*;
 use substrate_fixed::types::U96F32;
 
@@ -79,6 +80,65 @@ impl<T: Config> Pallet<T> {
             .sum::<u64>()
     }
 
+    // Returns the distinct subnets in which a coldkey holds any stake, in ascending order
+    //
+    pub fn get_coldkey_subnets(coldkey: &T::AccountId) -> Vec<u16> {
+        let mut netuids: Vec<u16> = Vec::new();
+        for hotkey in StakingHotkeys::<T>::get(coldkey) {
+            for (netuid, _) in Alpha::<T>::iter_prefix((&hotkey, coldkey)) {
+                if !netuids.contains(&netuid)
+                    && Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, coldkey, netuid)
+                        > 0
+                {
+                    netuids.push(netuid);
+                }
+            }
+        }
+        netuids.sort_unstable();
+        netuids
+    }
+
+    // Returns the hotkeys a coldkey is staking to
+    //
+    pub fn get_staking_hotkeys(coldkey: &T::AccountId) -> Vec<T::AccountId> {
+        StakingHotkeys::<T>::get(coldkey)
+    }
+
+    // Returns the hotkeys a coldkey has nonzero stake with on the given subnet
+    //
+    pub fn get_staking_hotkeys_on_subnet(coldkey: &T::AccountId, netuid: u16) -> Vec<T::AccountId> {
+        StakingHotkeys::<T>::get(coldkey)
+            .into_iter()
+            .filter(|hotkey| {
+                Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid) > 0
+            })
+            .collect()
+    }
+
+    // Returns the `n` coldkeys with the most alpha on a subnet, summed across their hotkeys,
+    // in descending order of stake. `n` is capped at MAX_TOP_STAKERS.
+    //
+    // WARN: This iterates over every entry of the Alpha map and is not meant for extrinsics.
+    //
+    pub fn get_top_stakers(netuid: u16, n: u32) -> Vec<(T::AccountId, u64)> {
+        let mut stakes: BTreeMap<T::AccountId, u64> = BTreeMap::new();
+        for (hotkey, coldkey, alpha_netuid) in Alpha::<T>::iter_keys() {
+            if alpha_netuid != netuid {
+                continue;
+            }
+            let alpha = Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+            if alpha > 0 {
+                let total = stakes.entry(coldkey).or_insert(0);
+                *total = total.saturating_add(alpha);
+            }
+        }
+
+        let mut top_stakers: Vec<(T::AccountId, u64)> = stakes.into_iter().collect();
+        top_stakers.sort_by(|(_, a), (_, b)| b.cmp(a));
+        top_stakers.truncate(n.min(MAX_TOP_STAKERS) as usize);
+        top_stakers
+    }
+
     // Creates a cold - hot pairing account if the hotkey is not already an active account.
     //
     pub fn create_account_if_non_existent(coldkey: &T::AccountId, hotkey: &T::AccountId) {
@@ -0,0 +1,99 @@
+use super::*;
+use safe_math::*;
+use substrate_fixed::types::U96F32;
+
+impl<T: Config> Pallet<T> {
+    /// Injects TAO and alpha directly into a subnet's pool reserves without a swap
+    ///
+    /// The TAO is withdrawn from the subnet owner's coldkey and the alpha is taken from the
+    /// owner's stake on the subnet owner hotkey, moving it from `SubnetAlphaOut` back into
+    /// `SubnetAlphaIn`, so no alpha is issued. The amounts must be in the same ratio as the
+    /// current reserves, within a 1% tolerance, so that the alpha price is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The origin of the call (must be signed by the subnet owner)
+    /// * `netuid` - The subnet ID
+    /// * `tao` - The amount of TAO to add to `SubnetTAO`
+    /// * `alpha` - The amount of the owner's alpha stake to move into `SubnetAlphaIn`
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Success or error
+    pub(crate) fn do_inject_liquidity(
+        origin: T::RuntimeOrigin,
+        netuid: u16,
+        tao: u64,
+        alpha: u64,
+    ) -> DispatchResult {
+        Self::ensure_subnet_owner(origin.clone(), netuid)?;
+        let coldkey: T::AccountId = ensure_signed(origin)?;
+
+        ensure!(Self::if_subnet_exist(netuid), Error::<T>::SubnetNotExists);
+        ensure!(tao > 0 && alpha > 0, Error::<T>::AmountTooLow);
+
+        // The price is only defined for a pool that already holds both reserves.
+        let tao_reserve = U96F32::saturating_from_num(SubnetTAO::<T>::get(netuid));
+        let alpha_reserve = U96F32::saturating_from_num(SubnetAlphaIn::<T>::get(netuid));
+        ensure!(
+            tao_reserve > U96F32::saturating_from_num(0)
+                && alpha_reserve > U96F32::saturating_from_num(0),
+            Error::<T>::InsufficientLiquidity
+        );
+
+        // Ensure the injection keeps the reserve ratio, and thus the price, within 1%.
+        let price_before = tao_reserve.safe_div(alpha_reserve);
+        let price_after = tao_reserve
+            .saturating_add(U96F32::saturating_from_num(tao))
+            .safe_div(alpha_reserve.saturating_add(U96F32::saturating_from_num(alpha)));
+        let max_deviation = price_before.saturating_mul(U96F32::saturating_from_num(0.01));
+        let deviation = if price_after > price_before {
+            price_after.saturating_sub(price_before)
+        } else {
+            price_before.saturating_sub(price_after)
+        };
+        ensure!(
+            deviation <= max_deviation,
+            Error::<T>::LiquidityInjectionChangesPrice
+        );
+
+        ensure!(
+            Self::can_remove_balance_from_coldkey_account(&coldkey, tao),
+            Error::<T>::NotEnoughBalanceToStake
+        );
+        let owner_hotkey = SubnetOwnerHotkey::<T>::get(netuid);
+        ensure!(
+            Self::get_stake_for_hotkey_and_coldkey_on_subnet(&owner_hotkey, &coldkey, netuid)
+                >= alpha,
+            Error::<T>::NotEnoughStakeToWithdraw
+        );
+
+        let tao_injected = Self::remove_balance_from_coldkey_account(&coldkey, tao)?;
+        let alpha_injected = Self::decrease_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &coldkey,
+            netuid,
+            alpha,
+        );
+
+        SubnetTAO::<T>::mutate(netuid, |total| {
+            *total = total.saturating_add(tao_injected);
+        });
+        SubnetAlphaOut::<T>::mutate(netuid, |total| {
+            *total = total.saturating_sub(alpha_injected);
+        });
+        SubnetAlphaIn::<T>::mutate(netuid, |total| {
+            *total = total.saturating_add(alpha_injected);
+        });
+        Self::increase_total_stake(tao_injected);
+
+        Self::deposit_event(Event::LiquidityInjected(
+            coldkey,
+            netuid,
+            tao_injected,
+            alpha_injected,
+        ));
+
+        Ok(())
+    }
+}
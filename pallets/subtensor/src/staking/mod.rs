@@ -2,8 +2,11 @@ use super::*;
 pub mod account;
 pub mod add_stake;
 pub mod decrease_take;
+pub mod default_slippage;
+pub mod delegation_allowlist;
 pub mod helpers;
 pub mod increase_take;
+pub mod inject_liquidity;
 pub mod move_stake;
 pub mod recycle_alpha;
 pub mod remove_stake;
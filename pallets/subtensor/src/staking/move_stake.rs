@@ -47,6 +47,8 @@ impl<T: Config> Pallet<T> {
             alpha_amount,
             None,
             None,
+            None,
+            None,
             false,
         )?;
 
@@ -129,6 +131,8 @@ impl<T: Config> Pallet<T> {
             alpha_amount,
             None,
             None,
+            None,
+            None,
             true,
         )?;
 
@@ -199,6 +203,8 @@ impl<T: Config> Pallet<T> {
             alpha_amount,
             None,
             None,
+            None,
+            None,
             false,
         )?;
 
@@ -271,6 +277,8 @@ impl<T: Config> Pallet<T> {
             alpha_amount,
             Some(limit_price),
             Some(allow_partial),
+            None,
+            None,
             false,
         )?;
 
@@ -295,8 +303,355 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Moves stake from one hotkey to another across subnets, rejecting the operation if the
+    /// staking fee exceeds `max_fee`.
+    ///
+    /// # Arguments
+    /// * `origin` - The origin of the transaction, which must be signed by the coldkey.
+    /// * `origin_hotkey` - The account ID of the hotkey from which the stake is being moved.
+    /// * `destination_hotkey` - The account ID of the hotkey to which the stake is being moved.
+    /// * `origin_netuid` - The network ID of the origin subnet.
+    /// * `destination_netuid` - The network ID of the destination subnet.
+    /// * `alpha_amount` - The amount of stake to move.
+    /// * `max_fee` - The maximum total fee (in TAO) the caller is willing to pay.
+    ///
+    /// # Errors
+    /// Same as `do_move_stake`, and additionally:
+    /// * `FeeExceedsMaximum` if the computed fee is greater than `max_fee`.
+    ///
+    /// # Events
+    /// Emits a `StakeMoved` event upon successful completion of the stake movement.
+    pub fn do_move_stake_with_max_fee(
+        origin: T::RuntimeOrigin,
+        origin_hotkey: T::AccountId,
+        destination_hotkey: T::AccountId,
+        origin_netuid: u16,
+        destination_netuid: u16,
+        alpha_amount: u64,
+        max_fee: u64,
+    ) -> dispatch::DispatchResult {
+        // Check that the origin is signed by the coldkey.
+        let coldkey = ensure_signed(origin)?;
+
+        // Validate input and move stake
+        let tao_moved = Self::transition_stake_internal(
+            &coldkey,
+            &coldkey,
+            &origin_hotkey,
+            &destination_hotkey,
+            origin_netuid,
+            destination_netuid,
+            alpha_amount,
+            None,
+            None,
+            Some(max_fee),
+            None,
+            false,
+        )?;
+
+        // Log the event.
+        log::debug!(
+            "StakeMoved( coldkey:{:?}, origin_hotkey:{:?}, origin_netuid:{:?}, destination_hotkey:{:?}, destination_netuid:{:?} )",
+            coldkey.clone(),
+            origin_hotkey.clone(),
+            origin_netuid,
+            destination_hotkey.clone(),
+            destination_netuid
+        );
+        Self::deposit_event(Event::StakeMoved(
+            coldkey,
+            origin_hotkey,
+            origin_netuid,
+            destination_hotkey,
+            destination_netuid,
+            tao_moved,
+        ));
+
+        // Ok and return.
+        Ok(())
+    }
+
+    /// Moves stake from one hotkey to another across subnets, rejecting the operation if less
+    /// than `min_dest_alpha` would be staked on the destination subnet.
+    ///
+    /// # Arguments
+    /// * `origin` - The origin of the transaction, which must be signed by the coldkey.
+    /// * `origin_hotkey` - The account ID of the hotkey from which the stake is being moved.
+    /// * `destination_hotkey` - The account ID of the hotkey to which the stake is being moved.
+    /// * `origin_netuid` - The network ID of the origin subnet.
+    /// * `destination_netuid` - The network ID of the destination subnet.
+    /// * `alpha_amount` - The amount of stake to move.
+    /// * `min_dest_alpha` - The minimum alpha the caller accepts to receive on the destination.
+    ///
+    /// # Errors
+    /// Same as `do_move_stake`, and additionally:
+    /// * `DestinationAlphaTooLow` if less than `min_dest_alpha` would be staked.
+    ///
+    /// # Events
+    /// Emits a `StakeMoved` event upon successful completion of the stake movement.
+    pub fn do_move_stake_with_min_dest_alpha(
+        origin: T::RuntimeOrigin,
+        origin_hotkey: T::AccountId,
+        destination_hotkey: T::AccountId,
+        origin_netuid: u16,
+        destination_netuid: u16,
+        alpha_amount: u64,
+        min_dest_alpha: u64,
+    ) -> dispatch::DispatchResult {
+        // Check that the origin is signed by the coldkey.
+        let coldkey = ensure_signed(origin)?;
+
+        // Validate input and move stake
+        let tao_moved = Self::transition_stake_internal(
+            &coldkey,
+            &coldkey,
+            &origin_hotkey,
+            &destination_hotkey,
+            origin_netuid,
+            destination_netuid,
+            alpha_amount,
+            None,
+            None,
+            None,
+            Some(min_dest_alpha),
+            false,
+        )?;
+
+        // Log the event.
+        log::debug!(
+            "StakeMoved( coldkey:{:?}, origin_hotkey:{:?}, origin_netuid:{:?}, destination_hotkey:{:?}, destination_netuid:{:?} )",
+            coldkey.clone(),
+            origin_hotkey.clone(),
+            origin_netuid,
+            destination_hotkey.clone(),
+            destination_netuid
+        );
+        Self::deposit_event(Event::StakeMoved(
+            coldkey,
+            origin_hotkey,
+            origin_netuid,
+            destination_hotkey,
+            destination_netuid,
+            tao_moved,
+        ));
+
+        // Ok and return.
+        Ok(())
+    }
+
+    /// Moves stake between two hotkeys on the same subnet without swapping through the pool.
+    ///
+    /// The alpha stays on the subnet, so only the share pools of the two hotkeys change. The
+    /// caller pays `DefaultStakingFee`, taken in alpha at the spot price and sold into the pool,
+    /// and the TAO is handled like any other staking fee.
+    ///
+    /// # Arguments
+    /// * `origin` - The origin of the transaction, which must be signed by the coldkey.
+    /// * `from_hotkey` - The account ID of the hotkey from which the stake is being moved.
+    /// * `to_hotkey` - The account ID of the hotkey to which the stake is being moved.
+    /// * `netuid` - The network ID of the subnet.
+    /// * `alpha_amount` - The amount of stake to move.
+    ///
+    /// # Errors
+    /// Same as `do_move_stake`, and additionally:
+    /// * `DelegationNotAllowed` if the coldkey may not delegate to `to_hotkey`.
+    /// * `FeeExceedsUnstakeAmount` if the fee would consume the whole amount.
+    ///
+    /// # Events
+    /// Emits a `HotkeyStakeSwapped` event upon successful completion of the stake movement.
+    pub fn do_swap_hotkey_stake(
+        origin: T::RuntimeOrigin,
+        from_hotkey: T::AccountId,
+        to_hotkey: T::AccountId,
+        netuid: u16,
+        alpha_amount: u64,
+    ) -> dispatch::DispatchResult {
+        // Check that the origin is signed by the coldkey.
+        let coldkey = ensure_signed(origin)?;
+
+        // Validate input as a move within the subnet.
+        Self::validate_stake_transition(
+            &coldkey,
+            &coldkey,
+            &from_hotkey,
+            &to_hotkey,
+            netuid,
+            netuid,
+            alpha_amount,
+            alpha_amount,
+            None,
+            false,
+        )?;
+
+        // The fee is converted to alpha at the spot price.
+        let fee_alpha = U96F32::saturating_from_num(DefaultStakingFee::<T>::get())
+            .safe_div(Self::get_alpha_price(netuid))
+            .saturating_ceil()
+            .saturating_to_num::<u64>();
+        ensure!(
+            fee_alpha < alpha_amount,
+            Error::<T>::FeeExceedsUnstakeAmount
+        );
+
+        // Move the shares.
+        let from_alpha_before = TotalHotkeyAlpha::<T>::get(&from_hotkey, netuid);
+        let alpha_removed = Self::decrease_stake_for_hotkey_and_coldkey_on_subnet(
+            &from_hotkey,
+            &coldkey,
+            netuid,
+            alpha_amount,
+        );
+        if from_alpha_before > 0 && TotalHotkeyAlpha::<T>::get(&from_hotkey, netuid) == 0 {
+            Self::deposit_event(Event::HotkeyExitedSubnet(from_hotkey.clone(), netuid));
+        }
+        let alpha_moved = alpha_removed.saturating_sub(fee_alpha);
+        let fee_charged = alpha_removed.saturating_sub(alpha_moved);
+        if Self::get_owning_coldkey_for_hotkey(&to_hotkey) != coldkey {
+            Self::maybe_become_delegate(&to_hotkey);
+        }
+        Self::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &to_hotkey,
+            &coldkey,
+            netuid,
+            alpha_moved,
+        );
+
+        // Sell the fee into the pool and credit the TAO like the fee of an unstake. The fee is
+        // recycled instead if the pool cannot take it.
+        let reserves_before = Self::get_audited_reserves(netuid);
+        let fee_tao = Self::swap_alpha_for_tao(netuid, fee_charged);
+        if fee_tao > 0 {
+            Self::record_swap(
+                netuid,
+                false,
+                reserves_before,
+                fee_charged,
+                fee_tao,
+                fee_tao,
+            );
+            Self::record_position_fee(&coldkey, &from_hotkey, netuid, fee_tao);
+            Self::credit_fee(&from_hotkey, netuid, fee_tao);
+        } else {
+            SubnetAlphaOut::<T>::mutate(netuid, |total| {
+                *total = total.saturating_sub(fee_charged);
+            });
+        }
+
+        // Update the hotkeys staked by the coldkey.
+        StakingHotkeys::<T>::mutate(&coldkey, |hotkeys| {
+            if !hotkeys.contains(&to_hotkey) {
+                hotkeys.push(to_hotkey.clone());
+            }
+            if Alpha::<T>::iter_prefix((&from_hotkey, &coldkey)).all(|(netuid, _)| {
+                Self::get_stake_for_hotkey_and_coldkey_on_subnet(&from_hotkey, &coldkey, netuid)
+                    == 0
+            }) {
+                hotkeys.retain(|k| *k != from_hotkey);
+            }
+        });
+        LastColdkeyHotkeyStakeBlock::<T>::insert(
+            &coldkey,
+            &to_hotkey,
+            Self::get_current_block_as_u64(),
+        );
+
+        // Log the event.
+        log::debug!(
+            "HotkeyStakeSwapped( coldkey:{:?}, from_hotkey:{:?}, to_hotkey:{:?}, netuid:{:?}, alpha:{:?}, fee:{:?} )",
+            coldkey,
+            from_hotkey,
+            to_hotkey,
+            netuid,
+            alpha_moved,
+            fee_charged
+        );
+        Self::deposit_event(Event::HotkeyStakeSwapped(
+            coldkey,
+            from_hotkey,
+            to_hotkey,
+            netuid,
+            alpha_moved,
+            fee_charged,
+        ));
+
+        // Ok and return.
+        Ok(())
+    }
+
+    /// Transfers stake from one coldkey to another, optionally moving from one subnet to another,
+    /// rejecting the operation if the staking fee exceeds `max_fee`.
+    ///
+    /// # Arguments
+    /// * `origin` - The origin of the transaction, which must be signed by the `origin_coldkey`.
+    /// * `destination_coldkey` - The account ID of the coldkey to which the stake is being transferred.
+    /// * `hotkey` - The account ID of the hotkey associated with this stake.
+    /// * `origin_netuid` - The network ID (subnet) from which the stake is being transferred.
+    /// * `destination_netuid` - The network ID (subnet) to which the stake is being transferred.
+    /// * `alpha_amount` - The amount of stake to transfer.
+    /// * `max_fee` - The maximum total fee (in TAO) the caller is willing to pay.
+    ///
+    /// # Errors
+    /// Same as `do_transfer_stake`, and additionally:
+    /// * `FeeExceedsMaximum` if the computed fee is greater than `max_fee`.
+    ///
+    /// # Events
+    /// Emits a `StakeTransferred` event upon successful completion of the transfer.
+    pub fn do_transfer_stake_with_max_fee(
+        origin: T::RuntimeOrigin,
+        destination_coldkey: T::AccountId,
+        hotkey: T::AccountId,
+        origin_netuid: u16,
+        destination_netuid: u16,
+        alpha_amount: u64,
+        max_fee: u64,
+    ) -> dispatch::DispatchResult {
+        // Ensure the extrinsic is signed by the origin_coldkey.
+        let coldkey = ensure_signed(origin)?;
+
+        // Validate input and move stake
+        let tao_moved = Self::transition_stake_internal(
+            &coldkey,
+            &destination_coldkey,
+            &hotkey,
+            &hotkey,
+            origin_netuid,
+            destination_netuid,
+            alpha_amount,
+            None,
+            None,
+            Some(max_fee),
+            None,
+            true,
+        )?;
+
+        // Emit an event for logging/monitoring.
+        log::debug!(
+            "StakeTransferred(origin_coldkey: {:?}, destination_coldkey: {:?}, hotkey: {:?}, origin_netuid: {:?}, destination_netuid: {:?}, amount: {:?})",
+            coldkey,
+            destination_coldkey,
+            hotkey,
+            origin_netuid,
+            destination_netuid,
+            tao_moved
+        );
+        Self::deposit_event(Event::StakeTransferred(
+            coldkey,
+            destination_coldkey,
+            hotkey,
+            origin_netuid,
+            destination_netuid,
+            tao_moved,
+        ));
+
+        // Return success.
+        Ok(())
+    }
+
     // If limit_price is None, this is a regular operation, otherwise, it is slippage-protected
-    // by setting limit price between origin_netuid and destination_netuid token
+    // by setting limit price between origin_netuid and destination_netuid token.
+    // If max_fee is Some, the operation is rejected when the total fee exceeds it.
+    // If min_dest_alpha is Some, the operation is rejected when less alpha is staked on the
+    // destination.
     fn transition_stake_internal(
         origin_coldkey: &T::AccountId,
         destination_coldkey: &T::AccountId,
@@ -307,6 +662,8 @@ impl<T: Config> Pallet<T> {
         alpha_amount: u64,
         maybe_limit_price: Option<u64>,
         maybe_allow_partial: Option<bool>,
+        maybe_max_fee: Option<u64>,
+        maybe_min_dest_alpha: Option<u64>,
         check_transfer_toggle: bool,
     ) -> Result<u64, Error<T>> {
         // Calculate the maximum amount that can be executed
@@ -338,14 +695,42 @@ impl<T: Config> Pallet<T> {
         };
 
         // Unstake from the origin subnet, returning TAO (or a 1:1 equivalent).
-        let fee = Self::calculate_staking_fee(
+        let total_fee = Self::calculate_staking_fee(
             Some((origin_hotkey, origin_netuid)),
             origin_coldkey,
             Some((destination_hotkey, destination_netuid)),
             destination_coldkey,
             U96F32::saturating_from_num(alpha_amount),
-        )
-        .safe_div(2);
+        );
+
+        // Ensure the fee is within the cap set by the caller, if any.
+        if let Some(max_fee) = maybe_max_fee {
+            ensure!(total_fee <= max_fee, Error::<T>::FeeExceedsMaximum);
+        }
+
+        // The fee is split between the unstake and the stake.
+        let fee = total_fee.safe_div(2);
+        ensure!(
+            !Self::fee_exceeds_unstake_amount(origin_netuid, move_amount, fee),
+            Error::<T>::FeeExceedsUnstakeAmount
+        );
+
+        // Ensure the destination receives at least the minimum alpha set by the caller, if any.
+        // The destination price moves independently of the origin, so the alpha is simulated
+        // through both legs, each paying its half of the fee.
+        if let Some(min_dest_alpha) = maybe_min_dest_alpha {
+            let expected_dest_alpha = Self::sim_swap_alpha_for_tao(origin_netuid, move_amount)
+                .map(|tao| tao.saturating_sub(fee))
+                .filter(|tao| *tao >= DefaultMinStake::<T>::get().saturating_add(fee))
+                .and_then(|tao| {
+                    Self::sim_swap_tao_for_alpha(destination_netuid, tao.saturating_sub(fee))
+                })
+                .unwrap_or(0);
+            ensure!(
+                expected_dest_alpha >= min_dest_alpha,
+                Error::<T>::DestinationAlphaTooLow
+            );
+        }
 
         let tao_unstaked = Self::unstake_from_subnet(
             origin_hotkey,
@@ -370,7 +755,7 @@ impl<T: Config> Pallet<T> {
                 destination_netuid,
                 tao_unstaked,
                 fee,
-            );
+            )?;
         }
 
         Ok(tao_unstaked.saturating_sub(fee))
@@ -1,4 +1,5 @@
 use super::*;
+use safe_math::*;
 use substrate_fixed::types::U96F32;
 
 impl<T: Config> Pallet<T> {
@@ -31,6 +32,12 @@ impl<T: Config> Pallet<T> {
     /// * 'TxRateLimitExceeded':
     ///     -  Thrown if key has hit transaction rate limit
     ///
+    /// * 'FeeExceedsUnstakeAmount':
+    ///     -  Thrown if the staking fee would consume all of the unstaked TAO.
+    ///
+    /// * 'FeeDisproportionate':
+    ///     -  Thrown if the staking fee exceeds the maximum share of the unstaked TAO.
+    ///
     pub fn do_remove_stake(
         origin: T::RuntimeOrigin,
         hotkey: T::AccountId,
@@ -47,15 +54,9 @@ impl<T: Config> Pallet<T> {
             alpha_unstaked
         );
 
-        // 2. Validate the user input
-        Self::validate_remove_stake(
-            &coldkey,
-            &hotkey,
-            netuid,
-            alpha_unstaked,
-            alpha_unstaked,
-            false,
-        )?;
+        // 2. Validate the user input, bounded by the coldkey's default slippage if it has one
+        let max_amount = Self::get_default_max_amount(&coldkey, netuid, alpha_unstaked, false);
+        Self::validate_remove_stake(&coldkey, &hotkey, netuid, alpha_unstaked, max_amount, false)?;
 
         // 3. Swap the alpba to tao and update counters for this subnet.
         let fee = Self::calculate_staking_fee(
@@ -65,6 +66,17 @@ impl<T: Config> Pallet<T> {
             &coldkey,
             U96F32::saturating_from_num(alpha_unstaked),
         );
+        ensure!(
+            !Self::fee_exceeds_unstake_amount(netuid, alpha_unstaked, fee),
+            Error::<T>::FeeExceedsUnstakeAmount
+        );
+        ensure!(
+            !Self::fee_is_disproportionate(
+                fee,
+                Self::sim_swap_alpha_for_tao(netuid, alpha_unstaked).unwrap_or(0)
+            ),
+            Error::<T>::FeeDisproportionate
+        );
         let tao_unstaked: u64 =
             Self::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha_unstaked, fee);
 
@@ -85,6 +97,110 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// ---- The implementation for the extrinsic remove_stake_tao: Removes the stake worth a
+    /// TAO amount from a hotkey account.
+    ///
+    /// The alpha to unstake is the amount that swaps for `tao_target` before fees, as computed
+    /// by `alpha_needed_for_tao`. The unstake itself is performed by `do_remove_stake`.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The associated hotkey account.
+    ///
+    /// * 'netuid' (u16):
+    ///     -  Subnetwork UID
+    ///
+    /// * 'tao_target' (u64):
+    ///     -  The TAO value of the stake to be removed, before fees.
+    ///
+    /// * 'allow_partial' (bool):
+    ///     -  Allows unstaking all of the stake if it is worth less than `tao_target`, or if the
+    ///        pool cannot pay out `tao_target`.
+    ///
+    /// # Raises:
+    /// * 'InsufficientLiquidity':
+    ///     -  Thrown if the pool cannot pay out `tao_target` and partial execution is not
+    ///        allowed.
+    ///
+    /// * 'NotEnoughStakeToWithdraw':
+    ///     -  Thrown if the stake is worth less than `tao_target` and partial execution is not
+    ///        allowed.
+    ///
+    /// Raises the same errors as `do_remove_stake` otherwise.
+    ///
+    pub fn do_remove_stake_tao(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        netuid: u16,
+        tao_target: u64,
+        allow_partial: bool,
+    ) -> dispatch::DispatchResult {
+        // 1. We check the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
+        let coldkey = ensure_signed(origin.clone())?;
+        log::debug!(
+            "do_remove_stake_tao( origin:{:?} hotkey:{:?}, netuid: {:?}, tao_target:{:?} )",
+            coldkey,
+            hotkey,
+            netuid,
+            tao_target
+        );
+
+        // 2. Convert the TAO target to the alpha that swaps for it, bounded by the stake of the
+        // coldkey. With partial execution, fall back to the full stake if the target is out of reach.
+        let alpha_available =
+            Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        let alpha_unstaked = match Self::alpha_needed_for_tao(netuid, tao_target) {
+            Some(alpha_needed) if alpha_needed <= alpha_available => alpha_needed,
+            Some(_) => {
+                ensure!(allow_partial, Error::<T>::NotEnoughStakeToWithdraw);
+                alpha_available
+            }
+            None => {
+                ensure!(allow_partial, Error::<T>::InsufficientLiquidity);
+                alpha_available
+            }
+        };
+
+        // 3. Unstake the alpha.
+        Self::do_remove_stake(origin, hotkey, netuid, alpha_unstaked)
+    }
+
+    /// ---- The implementation for the extrinsic remove_stake_all_for_subnet: Removes the full stake of a coldkey on a hotkey on one subnet.
+    ///
+    /// The amount is resolved from the share pool at execution, so the position is cleared
+    /// without leaving dust from a rounded client side balance.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The associated hotkey account.
+    ///
+    /// * 'netuid' (u16):
+    ///     -  Subnetwork UID
+    ///
+    /// # Event:
+    /// * StakeRemoved;
+    ///     -  On the successfully removing stake from the hotkey account.
+    ///
+    /// # Raises:
+    /// * Same as `do_remove_stake`.
+    ///
+    pub fn do_remove_stake_all_for_subnet(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        netuid: u16,
+    ) -> dispatch::DispatchResult {
+        let coldkey = ensure_signed(origin.clone())?;
+        let alpha_unstaked =
+            Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        Self::do_remove_stake(origin, hotkey, netuid, alpha_unstaked)
+    }
+
     /// ---- The implementation for the extrinsic unstake_all: Removes all stake from a hotkey account across all subnets and adds it onto a coldkey.
     ///
     /// # Args:
@@ -156,6 +272,10 @@ impl<T: Config> Pallet<T> {
                 &coldkey,
                 U96F32::saturating_from_num(alpha_unstaked),
             );
+            if Self::fee_exceeds_unstake_amount(netuid, alpha_unstaked, fee) {
+                // Don't unstake from this netuid
+                continue;
+            }
 
             if alpha_unstaked > 0 {
                 // Swap the alpha to tao and update counters for this subnet.
@@ -248,6 +368,10 @@ impl<T: Config> Pallet<T> {
                     &coldkey,
                     U96F32::saturating_from_num(alpha_unstaked),
                 );
+                if Self::fee_exceeds_unstake_amount(netuid, alpha_unstaked, fee) {
+                    // Don't unstake from this netuid
+                    continue;
+                }
 
                 if alpha_unstaked > 0 {
                     // Swap the alpha to tao and update counters for this subnet.
@@ -270,12 +394,119 @@ impl<T: Config> Pallet<T> {
             Self::get_root_netuid(),
             total_tao_unstaked,
             0, // no fee for restaking
-        );
+        )?;
 
         // 5. Done and ok.
         Ok(())
     }
 
+    /// ---- The implementation for the extrinsic reduce_all_positions: Unstakes the same share of
+    /// every position held by a coldkey, across all hotkeys and subnets.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'reduce_bps' (u16):
+    ///     -  The share of each position to unstake, in basis points.
+    ///
+    /// # Event:
+    /// * PositionsReduced;
+    ///     -  On successfully reducing the positions, with the total TAO recovered.
+    ///
+    /// # Raises:
+    /// * 'InvalidReduceBps':
+    ///     -  Thrown if reduce_bps is greater than 10000.
+    ///
+    /// * 'TooManyPositions':
+    ///     -  Thrown if the coldkey holds more than MAX_REDUCE_POSITIONS positions.
+    ///
+    pub fn do_reduce_all_positions(
+        origin: T::RuntimeOrigin,
+        reduce_bps: u16,
+    ) -> dispatch::DispatchResult {
+        // 1. We check the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
+        let coldkey = ensure_signed(origin)?;
+        log::debug!(
+            "do_reduce_all_positions( origin:{:?} reduce_bps:{:?} )",
+            coldkey,
+            reduce_bps
+        );
+
+        // 2. Ensure the share is at most 100%.
+        ensure!(reduce_bps <= 10_000, Error::<T>::InvalidReduceBps);
+
+        // 3. Collect the positions of the coldkey, bounded by MAX_REDUCE_POSITIONS.
+        let mut positions: Vec<(T::AccountId, u16)> = Vec::new();
+        for hotkey in StakingHotkeys::<T>::get(&coldkey) {
+            for (netuid, _) in Alpha::<T>::iter_prefix((&hotkey, &coldkey)) {
+                ensure!(
+                    positions.len() < MAX_REDUCE_POSITIONS as usize,
+                    Error::<T>::TooManyPositions
+                );
+                positions.push((hotkey.clone(), netuid));
+            }
+        }
+
+        // 4. Unstake the share of each position.
+        let mut total_tao_unstaked: u64 = 0;
+        for (hotkey, netuid) in positions {
+            let alpha_unstaked = U96F32::saturating_from_num(
+                Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid),
+            )
+            .saturating_mul(U96F32::saturating_from_num(reduce_bps))
+            .safe_div(U96F32::saturating_from_num(10_000))
+            .saturating_to_num::<u64>();
+
+            if alpha_unstaked == 0
+                || Self::validate_remove_stake(
+                    &coldkey,
+                    &hotkey,
+                    netuid,
+                    alpha_unstaked,
+                    alpha_unstaked,
+                    false,
+                )
+                .is_err()
+            {
+                // Don't unstake from this position
+                continue;
+            }
+
+            let fee = Self::calculate_staking_fee(
+                Some((&hotkey, netuid)),
+                &coldkey,
+                None,
+                &coldkey,
+                U96F32::saturating_from_num(alpha_unstaked),
+            );
+            if Self::fee_exceeds_unstake_amount(netuid, alpha_unstaked, fee) {
+                continue;
+            }
+
+            // Swap the alpha to tao and update counters for this subnet.
+            let tao_unstaked =
+                Self::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha_unstaked, fee);
+
+            // Add the balance to the coldkey. If the above fails we will not credit this coldkey.
+            Self::add_balance_to_coldkey_account(&coldkey, tao_unstaked);
+            total_tao_unstaked = total_tao_unstaked.saturating_add(tao_unstaked);
+
+            // If the stake is below the minimum, we clear the nomination from storage.
+            Self::clear_small_nomination_if_required(&hotkey, &coldkey, netuid);
+        }
+
+        // 5. Emit the summary event.
+        Self::deposit_event(Event::PositionsReduced(
+            coldkey,
+            reduce_bps,
+            total_tao_unstaked,
+        ));
+
+        // 6. Done and ok.
+        Ok(())
+    }
+
     /// ---- The implementation for the extrinsic remove_stake_limit: Removes stake from
     /// a hotkey on a subnet with a price limit.
     ///
@@ -314,6 +545,9 @@ impl<T: Config> Pallet<T> {
     /// * 'NotEnoughStakeToWithdraw':
     ///     - Thrown if there is not enough stake on the hotkey to withdwraw this amount.
     ///
+    /// * 'FeeExceedsUnstakeAmount':
+    ///     - Thrown if the staking fee would consume all of the unstaked TAO.
+    ///
     pub fn do_remove_stake_limit(
         origin: T::RuntimeOrigin,
         hotkey: T::AccountId,
@@ -332,7 +566,11 @@ impl<T: Config> Pallet<T> {
             alpha_unstaked
         );
 
+        // The subnet may force partial execution of all limit orders.
+        let allow_partial = allow_partial || ForcePartialFills::<T>::get(netuid);
+
         // 2. Calcaulate the maximum amount that can be executed with price limit
+        let limit_price = Self::apply_limit_price_epsilon(limit_price, false);
         let max_amount = Self::get_max_amount_remove(netuid, limit_price);
         let mut possible_alpha = alpha_unstaked;
         if possible_alpha > max_amount {
@@ -357,6 +595,17 @@ impl<T: Config> Pallet<T> {
             &coldkey,
             U96F32::saturating_from_num(alpha_unstaked),
         );
+        ensure!(
+            !Self::fee_exceeds_unstake_amount(netuid, possible_alpha, fee),
+            Error::<T>::FeeExceedsUnstakeAmount
+        );
+        ensure!(
+            !Self::fee_is_disproportionate(
+                fee,
+                Self::sim_swap_alpha_for_tao(netuid, possible_alpha).unwrap_or(0)
+            ),
+            Error::<T>::FeeDisproportionate
+        );
         let tao_unstaked =
             Self::unstake_from_subnet(&hotkey, &coldkey, netuid, possible_alpha, fee);
 
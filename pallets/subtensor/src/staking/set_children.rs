@@ -32,6 +32,10 @@ impl<T: Config> Pallet<T> {
     ///     - The hotkey account does not exist.
     /// * `TooManyChildren`:
     ///     - Too many children in request
+    /// * `TooManyParents`:
+    ///     - A child would have too many parents
+    /// * `SelfReferentialDelegation`:
+    ///     - A child is the same as the hotkey.
     ///
     pub fn do_schedule_children(
         origin: T::RuntimeOrigin,
@@ -77,12 +81,15 @@ impl<T: Config> Pallet<T> {
             Error::<T>::NonAssociatedColdKey
         );
 
-        // Ensure that the number of children does not exceed 5.
-        ensure!(children.len() <= 5, Error::<T>::TooManyChildren);
+        // Ensure that the number of children does not exceed the maximum.
+        ensure!(
+            children.len() <= T::MaxChildren::get() as usize,
+            Error::<T>::TooManyChildren
+        );
 
         // Ensure that each child is not the hotkey.
         for (_, child_i) in &children {
-            ensure!(child_i != &hotkey, Error::<T>::InvalidChild);
+            ensure!(child_i != &hotkey, Error::<T>::SelfReferentialDelegation);
         }
         // Ensure that the sum of the proportions does not exceed u64::MAX.
         let _total_proportion: u64 = children
@@ -100,6 +107,18 @@ impl<T: Config> Pallet<T> {
             unique_children.push(child_i.clone());
         }
 
+        // Ensure that no child exceeds the maximum number of parents.
+        for (_, child_i) in &children {
+            let other_parents = ParentKeys::<T>::get(child_i, netuid)
+                .iter()
+                .filter(|(_, parent)| *parent != hotkey)
+                .count();
+            ensure!(
+                other_parents < T::MaxParents::get() as usize,
+                Error::<T>::TooManyParents
+            );
+        }
+
         // Check that the parent key has at least the minimum own stake
         // if children vector is not empty
         // (checking with check_weights_min_stake wouldn't work because it considers
@@ -1,9 +1,24 @@
 use super::*;
+use frame_support::weights::Weight;
 use safe_math::*;
 use share_pool::{SharePool, SharePoolDataOperations};
+use sp_core::Get;
+use sp_runtime::traits::IntegerSquareRoot;
 use sp_std::ops::Neg;
 use substrate_fixed::types::{I64F64, I96F32, U64F64, U96F32, U110F18};
 
+/// How price index functions treat dynamic subnets whose pool has no alpha reserve.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ZeroLiquidityPolicy {
+    /// Leave the subnet out of the index.
+    #[default]
+    Skip,
+    /// Include the subnet at a price of zero.
+    TreatAsZero,
+    /// Include the subnet at its moving price, the last price it traded around.
+    TreatAsPrevious,
+}
+
 impl<T: Config> Pallet<T> {
     /// Retrieves the total alpha issuance for a given subnet.
     ///
@@ -57,7 +72,782 @@ impl<T: Config> Pallet<T> {
             U96F32::saturating_from_num(SubnetMovingPrice::<T>::get(netuid))
         }
     }
+
+    /// Returns SubnetMovingPrice multiplied by `scale` as an integer, so that clients can read the
+    /// moving price without decoding I96F32 (e.g. scale 1_000_000_000 for nanounits).
+    /// Negative prices are clamped to zero.
+    pub fn get_moving_price_scaled(netuid: u16, scale: u64) -> u64 {
+        SubnetMovingPrice::<T>::get(netuid)
+            .saturating_mul(I96F32::saturating_from_num(scale))
+            .max(I96F32::saturating_from_num(0))
+            .saturating_to_num::<u64>()
+    }
+
+    /// Records the total alpha of every registered hotkey on a subnet for the current epoch.
+    ///
+    /// Hotkeys without alpha are skipped, and a zero retention, the default, disables snapshots.
+    /// Every epoch older than the last `HotkeyAlphaSnapshotRetention` snapshotted epochs is
+    /// pruned, including the epochs left behind when the retention is lowered or snapshots are
+    /// disabled, at most `HOTKEY_ALPHA_SNAPSHOT_PRUNE_EPOCHS` epochs per call. Returns the
+    /// weight consumed.
+    pub fn snapshot_hotkey_alpha(netuid: u16) -> Weight {
+        let retention = HotkeyAlphaSnapshotRetention::<T>::get();
+        let mut epoch = SubnetEpochIndex::<T>::get(netuid);
+        let mut weight = T::DbWeight::get().reads(2);
+
+        if retention > 0 {
+            for (_, hotkey) in Keys::<T>::iter_prefix(netuid) {
+                let alpha = TotalHotkeyAlpha::<T>::get(&hotkey, netuid);
+                weight.saturating_accrue(T::DbWeight::get().reads(2));
+                if alpha > 0 {
+                    HotkeyAlphaSnapshot::<T>::insert((netuid, epoch, &hotkey), alpha);
+                    weight.saturating_accrue(T::DbWeight::get().writes(1));
+                }
+            }
+            epoch = epoch.saturating_add(1);
+            SubnetEpochIndex::<T>::insert(netuid, epoch);
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+
+        // Prune the epochs from the last pruned one up to the first retained one.
+        let retained_from = epoch.saturating_sub(retention);
+        let mut next = HotkeyAlphaSnapshotPrunedUntil::<T>::get(netuid);
+        let mut epochs_left = HOTKEY_ALPHA_SNAPSHOT_PRUNE_EPOCHS;
+        weight.saturating_accrue(T::DbWeight::get().reads(1));
+        if next >= retained_from {
+            return weight;
+        }
+        while next < retained_from && epochs_left > 0 {
+            let removed =
+                HotkeyAlphaSnapshot::<T>::clear_prefix((netuid, next), u32::MAX, None).unique;
+            weight.saturating_accrue(T::DbWeight::get().writes(u64::from(removed)));
+            next = next.saturating_add(1);
+            epochs_left = epochs_left.saturating_sub(1);
+        }
+        HotkeyAlphaSnapshotPrunedUntil::<T>::insert(netuid, next);
+        weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Returns the total alpha a hotkey owned on a subnet at the end of an epoch, if a snapshot
+    /// was retained.
+    pub fn get_historical_hotkey_alpha(
+        hotkey: &T::AccountId,
+        netuid: u16,
+        epoch: u32,
+    ) -> Option<u64> {
+        HotkeyAlphaSnapshot::<T>::get((netuid, epoch, hotkey))
+    }
+
+    /// Returns the price of a subnet as used by the price index functions, or `None` if the
+    /// subnet is left out of the index.
+    ///
+    /// Dynamic subnets without alpha reserve are handled according to `policy`.
+    fn get_index_price(netuid: u16, policy: ZeroLiquidityPolicy) -> Option<U96F32> {
+        let is_stable = netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0;
+        if is_stable || SubnetAlphaIn::<T>::get(netuid) > 0 {
+            return Some(Self::get_alpha_price(netuid));
+        }
+        match policy {
+            ZeroLiquidityPolicy::Skip => None,
+            ZeroLiquidityPolicy::TreatAsZero => Some(U96F32::saturating_from_num(0)),
+            ZeroLiquidityPolicy::TreatAsPrevious => Some(Self::get_moving_alpha_price(netuid)),
+        }
+    }
+
+    /// Calculates a global alpha price index as the average of all subnet alpha prices,
+    /// weighted by each subnet's TAO reserve (`SubnetTAO`).
+    ///
+    /// # Arguments
+    /// * `include_stable` - If true, root and stable subnets are included at price 1.0,
+    ///   otherwise they are skipped.
+    /// * `policy` - How subnets without alpha reserve are priced.
+    ///
+    /// # Returns
+    /// * `U96F32` - The reserve-weighted average alpha price, or zero if no subnet has TAO reserves.
+    pub fn get_global_alpha_index(include_stable: bool, policy: ZeroLiquidityPolicy) -> U96F32 {
+        let mut weighted_price_sum = U96F32::saturating_from_num(0);
+        let mut total_tao = U96F32::saturating_from_num(0);
+        for netuid in Self::get_all_subnet_netuids() {
+            let is_stable =
+                netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0;
+            if is_stable && !include_stable {
+                continue;
+            }
+            let Some(price) = Self::get_index_price(netuid, policy) else {
+                continue;
+            };
+            let subnet_tao = U96F32::saturating_from_num(SubnetTAO::<T>::get(netuid));
+            weighted_price_sum =
+                weighted_price_sum.saturating_add(price.saturating_mul(subnet_tao));
+            total_tao = total_tao.saturating_add(subnet_tao);
+        }
+        weighted_price_sum.safe_div(total_tao)
+    }
+
+    /// Calculates the median alpha price across dynamic subnets, weighted by each subnet's TAO
+    /// reserve (`SubnetTAO`). Unlike `get_global_alpha_index`, a single subnet with an outlier
+    /// price cannot move the result unless it holds half of the reserves.
+    ///
+    /// Root and stable subnets are skipped.
+    ///
+    /// # Arguments
+    /// * `policy` - How subnets without alpha reserve are priced.
+    ///
+    /// # Returns
+    /// * `U96F32` - The reserve-weighted median alpha price, or zero if no subnet has TAO reserves.
+    pub fn get_weighted_median_price(policy: ZeroLiquidityPolicy) -> U96F32 {
+        let mut prices: Vec<(U96F32, U96F32)> = Self::get_all_subnet_netuids()
+            .into_iter()
+            .filter(|netuid| {
+                *netuid != Self::get_root_netuid() && SubnetMechanism::<T>::get(*netuid) != 0
+            })
+            .filter_map(|netuid| {
+                Some((
+                    Self::get_index_price(netuid, policy)?,
+                    U96F32::saturating_from_num(SubnetTAO::<T>::get(netuid)),
+                ))
+            })
+            .filter(|(_, subnet_tao)| *subnet_tao > U96F32::saturating_from_num(0))
+            .collect();
+        prices.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        // Walk up the sorted prices until half of the total reserves are covered
+        let half_tao = prices
+            .iter()
+            .fold(U96F32::saturating_from_num(0), |acc, (_, subnet_tao)| {
+                acc.saturating_add(*subnet_tao)
+            })
+            .safe_div(U96F32::saturating_from_num(2));
+        let mut cumulative_tao = U96F32::saturating_from_num(0);
+        for (price, subnet_tao) in prices {
+            cumulative_tao = cumulative_tao.saturating_add(subnet_tao);
+            if cumulative_tao >= half_tao {
+                return price;
+            }
+        }
+        U96F32::saturating_from_num(0)
+    }
+
+    /// Returns the dynamic subnets with their TAO reserve (`SubnetTAO`), sorted by the reserve.
+    ///
+    /// Root and stable subnets have no slippage and are skipped. Subnets with equal reserves are
+    /// ordered by netuid.
+    pub fn get_subnets_by_liquidity(descending: bool) -> Vec<(u16, u64)> {
+        let mut subnets: Vec<(u16, u64)> = Self::get_all_subnet_netuids()
+            .into_iter()
+            .filter(|netuid| {
+                *netuid != Self::get_root_netuid() && SubnetMechanism::<T>::get(*netuid) != 0
+            })
+            .map(|netuid| (netuid, SubnetTAO::<T>::get(netuid)))
+            .collect();
+        if descending {
+            subnets.sort_by(|(netuid_a, a), (netuid_b, b)| b.cmp(a).then(netuid_a.cmp(netuid_b)));
+        } else {
+            subnets.sort_by(|(netuid_a, a), (netuid_b, b)| a.cmp(b).then(netuid_a.cmp(netuid_b)));
+        }
+        subnets
+    }
+
+    /// Returns the total TAO value locked in a subnet: the TAO reserve plus the circulating alpha
+    /// (`SubnetAlphaOut`) valued at the current spot price.
+    ///
+    /// The alpha side is a mark-to-market notional value. It is not what the alpha would fetch if
+    /// sold, since unstaking it all would move the price along the pool curve.
+    pub fn get_subnet_tvl(netuid: u16) -> u64 {
+        let alpha_value = U96F32::saturating_from_num(SubnetAlphaOut::<T>::get(netuid))
+            .saturating_mul(Self::get_alpha_price(netuid))
+            .saturating_to_num::<u64>();
+        alpha_value.saturating_add(SubnetTAO::<T>::get(netuid))
+    }
+
+    /// Returns the share of all staked TAO (`TotalStake`) held in the TAO reserve of a subnet, as
+    /// a fraction between 0 and 1.
+    ///
+    /// Returns zero if nothing is staked.
+    pub fn get_subnet_tao_dominance(netuid: u16) -> U96F32 {
+        let total_stake = TotalStake::<T>::get();
+        if total_stake == 0 {
+            return U96F32::saturating_from_num(0);
+        }
+        U96F32::saturating_from_num(SubnetTAO::<T>::get(netuid))
+            .safe_div(U96F32::saturating_from_num(total_stake))
+    }
+
+    /// Returns the market cap of a subnet in TAO: the total alpha issuance valued at the current
+    /// spot price.
+    ///
+    /// Root and stable subnets have a price of 1.0, so their market cap equals their issuance.
+    pub fn get_alpha_market_cap(netuid: u16) -> u64 {
+        U96F32::saturating_from_num(Self::get_alpha_issuance(netuid))
+            .saturating_mul(Self::get_alpha_price(netuid))
+            .saturating_to_num::<u64>()
+    }
+
+    /// Returns the TAO and alpha reserves of a subnet in whole units rather than rao.
+    pub fn get_reserves_in_tao(netuid: u16) -> (U96F32, U96F32) {
+        let rao_per_tao = U96F32::saturating_from_num(RAO_PER_TAO);
+        (
+            U96F32::saturating_from_num(SubnetTAO::<T>::get(netuid)).safe_div(rao_per_tao),
+            U96F32::saturating_from_num(SubnetAlphaIn::<T>::get(netuid)).safe_div(rao_per_tao),
+        )
+    }
+
+    /// Returns the price of subnet `netuid_a` alpha denominated in subnet `netuid_b` alpha: the
+    /// amount of B alpha that one unit of A alpha is worth at the current spot prices.
+    ///
+    /// Returns zero when subnet B has no price.
+    pub fn get_cross_subnet_price(netuid_a: u16, netuid_b: u16) -> U96F32 {
+        let price_b = Self::get_alpha_price(netuid_b);
+        if price_b == U96F32::saturating_from_num(0) {
+            return U96F32::saturating_from_num(0);
+        }
+        Self::get_alpha_price(netuid_a).safe_div(price_b)
+    }
+
+    /// Returns the price elasticity of a subnet pool: how much the alpha price rises per TAO
+    /// added at the current reserves.
+    ///
+    /// For the constant product `tao * alpha = k`, the price after adding TAO is `tao^2 / k`, so
+    /// its derivative is `2 * tao / k = 2 / alpha`, scaled by 1e9 rao per TAO. Root and stable
+    /// subnets, and pools without alpha, return zero.
+    pub fn get_price_elasticity(netuid: u16) -> U96F32 {
+        let zero = U96F32::saturating_from_num(0);
+        if netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0 {
+            return zero;
+        }
+        let alpha_in = SubnetAlphaIn::<T>::get(netuid);
+        if alpha_in == 0 {
+            return zero;
+        }
+        U96F32::saturating_from_num(2_000_000_000_u64)
+            .safe_div(U96F32::saturating_from_num(alpha_in))
+    }
+
+    /// Returns the largest TAO stake whose execution price stays within `max_slippage_bps` basis
+    /// points above the current spot price.
+    ///
+    /// The execution price of a constant-product swap of `x` TAO is `(tao + x) / alpha`, so the
+    /// slippage is `x / tao` and the bound is solved through `get_max_amount_add` with the spot
+    /// price raised by the slippage. Root and stable subnets have no slippage and return
+    /// `u64::MAX`.
+    pub fn max_stake_within_slippage(netuid: u16, max_slippage_bps: u16) -> u64 {
+        if netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0 {
+            return u64::MAX;
+        }
+        let limit_price = Self::get_alpha_price(netuid)
+            .saturating_mul(U96F32::saturating_from_num(RAO_PER_TAO))
+            .saturating_mul(U96F32::saturating_from_num(
+                10_000_u32.saturating_add(u32::from(max_slippage_bps)),
+            ))
+            .safe_div(U96F32::saturating_from_num(10_000))
+            .saturating_to_num::<u64>();
+        Self::get_max_amount_add(netuid, limit_price)
+    }
+
+    /// Returns the divergence of a reserve position from holding its assets, after the alpha
+    /// price moved from `initial_price` to the current price.
+    ///
+    /// This is the constant-product impermanent loss `2 * sqrt(r) / (1 + r) - 1` with
+    /// `r = current_price / initial_price`. It is zero when the price is unchanged and negative
+    /// otherwise, down to -1.0. A zero initial price returns zero.
+    pub fn get_reserve_divergence(netuid: u16, initial_price: U96F32) -> I96F32 {
+        let zero = U96F32::saturating_from_num(0);
+        let one = U96F32::saturating_from_num(1);
+        if initial_price == zero {
+            return I96F32::saturating_from_num(0);
+        }
+
+        // The loss is the same for r and 1 / r, and the square root is only exact for r >= 1.
+        let current_price = Self::get_alpha_price(netuid);
+        let ratio = current_price
+            .safe_div(initial_price)
+            .max(initial_price.safe_div(current_price));
+        let sqrt_ratio = checked_sqrt(ratio, U96F32::saturating_from_num(0.000001)).unwrap_or(zero);
+        let value_ratio = sqrt_ratio
+            .saturating_mul(U96F32::saturating_from_num(2))
+            .safe_div(one.saturating_add(ratio));
+
+        I96F32::saturating_from_num(value_ratio).saturating_sub(I96F32::saturating_from_num(1))
+    }
+
+    /// Returns the difference between the spot and moving alpha price of a subnet.
+    ///
+    /// The value is positive when the spot price is above the moving price (uptrend) and
+    /// negative when it is below. Root and stable subnets return zero.
+    pub fn get_price_momentum(netuid: u16) -> I96F32 {
+        if netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0 {
+            return I96F32::saturating_from_num(0);
+        }
+        I96F32::saturating_from_num(Self::get_alpha_price(netuid)).saturating_sub(
+            I96F32::saturating_from_num(Self::get_moving_alpha_price(netuid)),
+        )
+    }
+
+    /// Computes the pool depth between the current price and a target price.
+    ///
+    /// Returns the TAO and alpha that would be traded along the constant-product curve to
+    /// move the price to `target_price`, expressed in RAO per one alpha. When the target is
+    /// above the current price the TAO is bought in and the alpha is taken out of the pool,
+    /// and the other way around when it is below. Both amounts are returned as absolute
+    /// values.
+    ///
+    /// Root and stable subnets, empty pools and a zero target price have no depth.
+    pub fn depth_to_price(netuid: u16, target_price: u64) -> (u64, u64) {
+        if netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0 {
+            return (0, 0);
+        }
+        let tao_reserve = SubnetTAO::<T>::get(netuid) as u128;
+        let alpha_reserve = SubnetAlphaIn::<T>::get(netuid) as u128;
+        if tao_reserve == 0 || alpha_reserve == 0 || target_price == 0 {
+            return (0, 0);
+        }
+
+        // On the curve tao * alpha = k the reserves at price p are tao = sqrt(k * p) and
+        // alpha = tao / p.
+        let rao_per_tao = 1_000_000_000_u128;
+        let target_price = target_price as u128;
+        let k = tao_reserve.saturating_mul(alpha_reserve);
+        let k_times_price = k
+            .checked_mul(target_price)
+            .and_then(|value| value.checked_div(rao_per_tao))
+            .unwrap_or_else(|| {
+                k.checked_div(rao_per_tao)
+                    .unwrap_or(0)
+                    .saturating_mul(target_price)
+            });
+        let target_tao_reserve = k_times_price.integer_sqrt();
+        let target_alpha_reserve = target_tao_reserve
+            .saturating_mul(rao_per_tao)
+            .checked_div(target_price)
+            .unwrap_or(0);
+
+        let tao = target_tao_reserve.abs_diff(tao_reserve);
+        let alpha = target_alpha_reserve.abs_diff(alpha_reserve);
+        (
+            u64::try_from(tao).unwrap_or(u64::MAX),
+            u64::try_from(alpha).unwrap_or(u64::MAX),
+        )
+    }
+
+    /// Simulates staking `total_tao` on a subnet in `tranches` equal parts and returns the alpha
+    /// price after each tranche. The last tranche also takes the remainder of the split.
+    ///
+    /// On the pool curve a sequence of swaps ends at the same price as one swap of the total, so
+    /// this only describes the path taken. Nothing is written to storage. Root and stable
+    /// subnets stay at price 1.0, and the path stops early once a tranche cannot be executed.
+    pub fn simulate_split_execution(netuid: u16, total_tao: u64, tranches: u32) -> Vec<U96F32> {
+        let Some(tranche_tao) = total_tao.checked_div(u64::from(tranches)) else {
+            return Vec::new();
+        };
+        if netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0 {
+            return (0..tranches)
+                .map(|_| U96F32::saturating_from_num(1))
+                .collect();
+        }
+
+        let mut tao_reserve = u128::from(SubnetTAO::<T>::get(netuid));
+        let mut alpha_reserve = u128::from(SubnetAlphaIn::<T>::get(netuid));
+        let last_tranche_tao = tranche_tao.saturating_add(
+            total_tao.saturating_sub(tranche_tao.saturating_mul(u64::from(tranches))),
+        );
+
+        let mut prices = Vec::new();
+        for tranche in 0..tranches {
+            let tao = if tranche == tranches.saturating_sub(1) {
+                last_tranche_tao
+            } else {
+                tranche_tao
+            };
+            let new_tao_reserve = tao_reserve.saturating_add(u128::from(tao));
+            let new_alpha_reserve = alpha_reserve
+                .checked_mul(tao_reserve)
+                .and_then(|k| k.checked_div(new_tao_reserve));
+            let Some(new_alpha_reserve) = new_alpha_reserve.filter(|reserve| *reserve > 0) else {
+                break;
+            };
+
+            tao_reserve = new_tao_reserve;
+            alpha_reserve = new_alpha_reserve;
+            prices.push(
+                U96F32::saturating_from_num(tao_reserve)
+                    .safe_div(U96F32::saturating_from_num(alpha_reserve)),
+            );
+        }
+        prices
+    }
+
+    /// Returns the relative spread between the effective buy and sell prices for a trade of
+    /// `size_tao` through a hotkey on a subnet.
+    ///
+    /// The buy leg stakes `size_tao` for alpha and the sell leg unstakes that alpha against the
+    /// same reserves, each paying the staking fee `add_stake` and `remove_stake` would charge.
+    /// The spread is `(buy_price - sell_price) / buy_price`, i.e. the share of `size_tao` lost on
+    /// a round trip. Returns 1 if the trade cannot be executed.
+    pub fn get_trade_spread(hotkey: &T::AccountId, netuid: u16, size_tao: u64) -> U96F32 {
+        // The fee does not depend on the coldkeys, so the hotkey stands in for both of them.
+        let buy_fee = Self::calculate_staking_fee(
+            None,
+            hotkey,
+            Some((hotkey, netuid)),
+            hotkey,
+            U96F32::saturating_from_num(size_tao),
+        );
+
+        let tao_returned = Self::sim_swap_tao_for_alpha(netuid, size_tao.saturating_sub(buy_fee))
+            .filter(|alpha| *alpha > 0)
+            .and_then(|alpha| {
+                let sell_fee = Self::calculate_staking_fee(
+                    Some((hotkey, netuid)),
+                    hotkey,
+                    None,
+                    hotkey,
+                    U96F32::saturating_from_num(alpha),
+                );
+                Self::sim_swap_alpha_for_tao(netuid, alpha).map(|tao| tao.saturating_sub(sell_fee))
+            });
+        let Some(tao_returned) = tao_returned else {
+            return U96F32::saturating_from_num(1);
+        };
+
+        // Both prices are quoted for the same alpha, so their ratio is tao_returned / size_tao.
+        U96F32::saturating_from_num(1).saturating_sub(
+            U96F32::saturating_from_num(tao_returned)
+                .safe_div(U96F32::saturating_from_num(size_tao)),
+        )
+    }
+
+    /// Returns true if swapping `tao` for `alpha` would leave the subnet price above its
+    /// `PriceCeiling`. Always false when no ceiling is set.
+    ///
+    /// The price of root and stable subnets does not move, so only the current price is checked.
+    pub fn exceeds_price_ceiling(netuid: u16, tao: u64, alpha: u64) -> bool {
+        let Some(ceiling) = PriceCeiling::<T>::get(netuid) else {
+            return false;
+        };
+        if netuid == Self::get_root_netuid() || SubnetMechanism::<T>::get(netuid) == 0 {
+            return Self::get_alpha_price(netuid) > ceiling;
+        }
+
+        let tao_reserve = SubnetTAO::<T>::get(netuid).saturating_add(tao);
+        let alpha_reserve = SubnetAlphaIn::<T>::get(netuid).saturating_sub(alpha);
+        if alpha_reserve == 0 {
+            return true;
+        }
+        let price_after = U96F32::saturating_from_num(tao_reserve)
+            .safe_div(U96F32::saturating_from_num(alpha_reserve));
+        price_after > ceiling
+    }
+
+    /// Widens a limit price by `LimitPriceEpsilon` in favor of the order: up for a buy and down
+    /// for a sell, so that prices within the epsilon of the limit still satisfy it.
+    ///
+    /// The default epsilon of zero keeps the strict comparison.
+    pub fn apply_limit_price_epsilon(limit_price: u64, is_buy: bool) -> u64 {
+        let epsilon = LimitPriceEpsilon::<T>::get();
+        if is_buy {
+            limit_price.saturating_add(epsilon)
+        } else {
+            limit_price.saturating_sub(epsilon)
+        }
+    }
+
+    /// Previews how much of a limit order fills before the price crosses `limit_price`.
+    ///
+    /// For a buy, `amount` is the TAO staked and the bound comes from `get_max_amount_add`. For
+    /// a sell, `amount` is the alpha unstaked and the bound comes from `get_max_amount_remove`.
+    ///
+    /// # Returns
+    /// * `(u64, u64)` - The fillable and unfillable parts of `amount`.
+    pub fn preview_limit_fill(
+        netuid: u16,
+        amount: u64,
+        limit_price: u64,
+        is_buy: bool,
+    ) -> (u64, u64) {
+        let max_amount = if is_buy {
+            Self::get_max_amount_add(netuid, limit_price)
+        } else {
+            Self::get_max_amount_remove(netuid, limit_price)
+        };
+        let fillable = amount.min(max_amount);
+        (fillable, amount.saturating_sub(fillable))
+    }
+
+    /// Estimates the annual yield of a coldkey's stake on a hotkey from the last epoch dividends.
+    ///
+    /// The coldkey's share of `AlphaDividendsPerSubnet` is its stake over the hotkey alpha the
+    /// dividends were computed on, so the per epoch yield is the dividends over
+    /// `TotalHotkeyAlphaLastEpoch`. This is annualized without compounding assuming constant
+    /// emission, 12 second blocks and one epoch every `tempo + 1` blocks. The fee of unstaking
+    /// the position once is deducted as a fraction of its TAO value.
+    ///
+    /// # Returns
+    /// * `U96F32` - The estimated yield, where 1.0 is 100%, or zero without stake.
+    pub fn estimate_delegator_apy(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: u16,
+    ) -> U96F32 {
+        let zero = U96F32::saturating_from_num(0);
+        let stake = Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid);
+        let hotkey_alpha = TotalHotkeyAlphaLastEpoch::<T>::get(hotkey, netuid);
+        if stake == 0 || hotkey_alpha == 0 {
+            return zero;
+        }
+
+        // Yield per epoch of the coldkey's share of the dividends.
+        let dividends =
+            U96F32::saturating_from_num(AlphaDividendsPerSubnet::<T>::get(netuid, hotkey));
+        let epoch_yield = dividends.safe_div(U96F32::saturating_from_num(hotkey_alpha));
+
+        // Annualize over the epochs in a year of 12 second blocks (365 days).
+        let blocks_per_year = U96F32::saturating_from_num(2_628_000);
+        let blocks_per_epoch =
+            U96F32::saturating_from_num(u64::from(Self::get_tempo(netuid)).saturating_add(1));
+        let annual_yield = epoch_yield.saturating_mul(blocks_per_year.safe_div(blocks_per_epoch));
+
+        // Deduct the fee drag of exiting the position.
+        let stake_tao = Self::sim_swap_alpha_for_tao(netuid, stake).unwrap_or(0);
+        let fee = Self::calculate_staking_fee(
+            Some((hotkey, netuid)),
+            coldkey,
+            None,
+            coldkey,
+            U96F32::saturating_from_num(stake),
+        );
+        let fee_drag = if stake_tao == 0 {
+            zero
+        } else {
+            U96F32::saturating_from_num(fee).safe_div(U96F32::saturating_from_num(stake_tao))
+        };
+
+        annual_yield.saturating_sub(fee_drag)
+    }
+
+    /// Estimates the annual cost of holding a coldkey's stake on a hotkey and exiting it: the
+    /// fee of unstaking plus the slippage of selling the position into the pool, as a fraction
+    /// of its value at the spot price.
+    ///
+    /// The exit slippage assumes the current position is unwound in one swap at the current
+    /// reserves. Like the fee drag of `estimate_delegator_apy`, the cost is incurred once and
+    /// counted against a year of holding, so it can be compared with that yield. A position
+    /// that cannot be sold returns 1.0 (100%).
+    ///
+    /// # Returns
+    /// * `U96F32` - The estimated cost, where 1.0 is 100%, or zero without stake.
+    pub fn get_position_cost_apy(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: u16,
+    ) -> U96F32 {
+        let zero = U96F32::saturating_from_num(0);
+        let one = U96F32::saturating_from_num(1);
+        let stake = Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid);
+        let spot_value =
+            U96F32::saturating_from_num(stake).saturating_mul(Self::get_alpha_price(netuid));
+        if spot_value == zero {
+            return zero;
+        }
+
+        // The TAO received for unwinding the whole position, net of the fee.
+        let Some(stake_tao) = Self::sim_swap_alpha_for_tao(netuid, stake) else {
+            return one;
+        };
+        let fee = Self::calculate_staking_fee(
+            Some((hotkey, netuid)),
+            coldkey,
+            None,
+            coldkey,
+            U96F32::saturating_from_num(stake),
+        );
+        let exit_value = U96F32::saturating_from_num(stake_tao.saturating_sub(fee));
+
+        spot_value
+            .saturating_sub(exit_value)
+            .safe_div(spot_value)
+            .min(one)
+    }
+
+    /// Returns the number of blocks a new stake of `tao` on a hotkey must be held for its
+    /// dividends to pay for the fees of staking and unstaking it.
+    ///
+    /// The yield per block is the last epoch dividends over `TotalHotkeyAlphaLastEpoch`, spread
+    /// over the `tempo + 1` blocks of an epoch, and is valued at the current spot price. Both
+    /// fees are computed by `calculate_staking_fee`, the exit fee on the alpha the stake would
+    /// receive at the current reserves. Returns None if the stake would earn no yield.
+    pub fn break_even_holding_blocks(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: u16,
+        tao: u64,
+    ) -> Option<u64> {
+        let zero = U96F32::saturating_from_num(0);
+        let hotkey_alpha = TotalHotkeyAlphaLastEpoch::<T>::get(hotkey, netuid);
+        if hotkey_alpha == 0 {
+            return None;
+        }
+
+        // The alpha received for the stake after the entry fee.
+        let entry_fee = Self::calculate_staking_fee(
+            None,
+            coldkey,
+            Some((hotkey, netuid)),
+            coldkey,
+            U96F32::saturating_from_num(tao),
+        );
+        let alpha = Self::sim_swap_tao_for_alpha(netuid, tao.saturating_sub(entry_fee))?;
+        let exit_fee = Self::calculate_staking_fee(
+            Some((hotkey, netuid)),
+            coldkey,
+            None,
+            coldkey,
+            U96F32::saturating_from_num(alpha),
+        );
+
+        // Yield of the stake per block, in TAO.
+        let dividends =
+            U96F32::saturating_from_num(AlphaDividendsPerSubnet::<T>::get(netuid, hotkey));
+        let blocks_per_epoch =
+            U96F32::saturating_from_num(u64::from(Self::get_tempo(netuid)).saturating_add(1));
+        let block_yield = U96F32::saturating_from_num(alpha)
+            .saturating_mul(dividends.safe_div(U96F32::saturating_from_num(hotkey_alpha)))
+            .safe_div(blocks_per_epoch)
+            .saturating_mul(Self::get_alpha_price(netuid));
+        if block_yield == zero {
+            return None;
+        }
+
+        Some(
+            U96F32::saturating_from_num(entry_fee.saturating_add(exit_fee))
+                .safe_div(block_yield)
+                .saturating_ceil()
+                .saturating_to_num::<u64>(),
+        )
+    }
+
+    /// Returns the TAO value of a coldkey's share of the last epoch dividends of a hotkey.
+    ///
+    /// The share is the coldkey's stake over `TotalHotkeyAlphaLastEpoch`, the hotkey alpha the
+    /// dividends were computed on. The value is notional: it is quoted along the pool curve at
+    /// the current reserves, without a fee, and changes with the price.
+    pub fn get_pending_dividend_value(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        netuid: u16,
+    ) -> u64 {
+        let stake = Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid);
+        let hotkey_alpha = TotalHotkeyAlphaLastEpoch::<T>::get(hotkey, netuid);
+        if stake == 0 || hotkey_alpha == 0 {
+            return 0;
+        }
+
+        let share = U96F32::saturating_from_num(stake)
+            .safe_div(U96F32::saturating_from_num(hotkey_alpha))
+            .min(U96F32::saturating_from_num(1));
+        let dividends =
+            U96F32::saturating_from_num(AlphaDividendsPerSubnet::<T>::get(netuid, hotkey))
+                .saturating_mul(share)
+                .saturating_to_num::<u64>();
+
+        Self::sim_swap_alpha_for_tao(netuid, dividends).unwrap_or(0)
+    }
+
+    /// Returns the per epoch yield a delegator to a hotkey needs to cover the fee of unstaking.
+    ///
+    /// The fee is computed by `calculate_staking_fee` for removing a position the size of the
+    /// hotkey's stake on the subnet, and returned as a fraction of the position's TAO value.
+    /// Below this yield an epoch of delegation does not pay for exiting it. Returns zero if the
+    /// hotkey has no stake on the subnet.
+    pub fn minimum_yield_to_offset_fees(hotkey: &T::AccountId, netuid: u16) -> U96F32 {
+        let zero = U96F32::saturating_from_num(0);
+        let alpha = TotalHotkeyAlpha::<T>::get(hotkey, netuid);
+        let tao_value = Self::sim_swap_alpha_for_tao(netuid, alpha).unwrap_or(0);
+        if tao_value == 0 {
+            return zero;
+        }
+
+        let coldkey = Self::get_owning_coldkey_for_hotkey(hotkey);
+        let fee = Self::calculate_staking_fee(
+            Some((hotkey, netuid)),
+            &coldkey,
+            None,
+            &coldkey,
+            U96F32::saturating_from_num(alpha),
+        );
+        U96F32::saturating_from_num(fee).safe_div(U96F32::saturating_from_num(tao_value))
+    }
+
+    /// Returns the alpha a new delegator must stake on a hotkey to own `target_share_bps` of its
+    /// shares on the subnet.
+    ///
+    /// Shares are issued in proportion to the alpha added, so staking `x` onto a pool of total
+    /// alpha `a` owns `x / (a + x)` of the shares, and the target share `s` needs
+    /// `x = a * s / (1 - s)`. A hotkey without alpha is owned entirely by any stake, so zero is
+    /// returned. A target of 10000 basis points or more is unreachable and returns `u64::MAX`.
+    pub fn stake_for_target_dividend_share(
+        hotkey: &T::AccountId,
+        netuid: u16,
+        target_share_bps: u16,
+    ) -> u64 {
+        let total_alpha = TotalHotkeyAlpha::<T>::get(hotkey, netuid);
+        if total_alpha == 0 || target_share_bps == 0 {
+            return 0;
+        }
+        if target_share_bps >= 10_000 {
+            return u64::MAX;
+        }
+
+        let target_share = U96F32::saturating_from_num(target_share_bps)
+            .safe_div(U96F32::saturating_from_num(10_000));
+        U96F32::saturating_from_num(total_alpha)
+            .saturating_mul(target_share)
+            .safe_div(U96F32::saturating_from_num(1).saturating_sub(target_share))
+            .saturating_ceil()
+            .saturating_to_num::<u64>()
+    }
+
+    /// Advances the moving price of a subnet by one step.
+    ///
+    /// The moving price is updated at most once per block; repeated calls within the same
+    /// block leave it unchanged.
     pub fn update_moving_price(netuid: u16) {
+        let current_block = Self::get_current_block_as_u64();
+        if LastMovingPriceUpdateBlock::<T>::get(netuid) == Some(current_block) {
+            return;
+        }
+
+        let (blocks_since_start_call, alpha, spot_price, new_moving) =
+            Self::blend_moving_price(netuid, Self::get_alpha_price(netuid));
+        SubnetMovingPrice::<T>::insert(netuid, new_moving);
+        LastMovingPriceUpdateBlock::<T>::insert(netuid, current_block);
+
+        if EmaDebugEvents::<T>::get() {
+            Self::deposit_event(Event::MovingPriceDebug(
+                netuid,
+                blocks_since_start_call.saturating_to_num::<u64>(),
+                alpha,
+                spot_price,
+                new_moving,
+            ));
+        }
+    }
+
+    /// Returns the moving price a subnet would have after the next update if its spot price were
+    /// `hypothetical_spot`, without persisting it.
+    ///
+    /// The spot price is clamped to 1.0 as in `update_moving_price`.
+    pub fn preview_next_moving_price(netuid: u16, hypothetical_spot: U96F32) -> I96F32 {
+        let (_, _, _, new_moving) = Self::blend_moving_price(netuid, hypothetical_spot);
+        new_moving
+    }
+
+    /// Blends a spot price into the moving price of a subnet at the current block.
+    ///
+    /// # Returns
+    /// * `(U96F32, U96F32, U96F32, I96F32)` - The blocks since `start_call`, the blending alpha,
+    ///   the clamped spot price and the new moving price.
+    fn blend_moving_price(netuid: u16, spot_price: U96F32) -> (U96F32, U96F32, U96F32, I96F32) {
+        let current_block = Self::get_current_block_as_u64();
         let blocks_since_start_call = U96F32::saturating_from_num({
             // We expect FirstEmissionBlockNumber to be set earlier, and we take the block when
             // `start_call` was called (first block before FirstEmissionBlockNumber).
@@ -65,7 +855,7 @@ impl<T: Config> Pallet<T> {
                 .unwrap_or_default()
                 .saturating_sub(1);
 
-            Self::get_current_block_as_u64().saturating_sub(start_call_block)
+            current_block.saturating_sub(start_call_block)
         });
 
         let halving_time = EMAPriceHalvingBlocks::<T>::get(netuid);
@@ -76,14 +866,50 @@ impl<T: Config> Pallet<T> {
         // Because alpha = b / (b + h), where b and h > 0, alpha < 1, so 1 - alpha > 0.
         // We can use unsigned type here: U96F32
         let one_minus_alpha: U96F32 = U96F32::saturating_from_num(1.0).saturating_sub(alpha);
-        let current_price: U96F32 = alpha
-            .saturating_mul(Self::get_alpha_price(netuid).min(U96F32::saturating_from_num(1.0)));
+        let spot_price: U96F32 = spot_price.min(U96F32::saturating_from_num(1.0));
+        let current_price: U96F32 = alpha.saturating_mul(spot_price);
         let current_moving: U96F32 =
             one_minus_alpha.saturating_mul(Self::get_moving_alpha_price(netuid));
         // Convert batch to signed I96F32 to avoid migration of SubnetMovingPrice for now``
         let new_moving: I96F32 =
             I96F32::saturating_from_num(current_price.saturating_add(current_moving));
-        SubnetMovingPrice::<T>::insert(netuid, new_moving);
+        (blocks_since_start_call, alpha, spot_price, new_moving)
+    }
+
+    /// Updates the moving price of every dynamic subnet that has started emitting.
+    ///
+    /// Root and stable subnets are skipped, since their moving price is always 1.0.
+    ///
+    /// # Returns
+    /// * `Weight` - The weight consumed by the update.
+    pub fn update_all_moving_prices() -> Weight {
+        let subnets = Self::get_all_subnet_netuids();
+        let mut updated: u64 = 0;
+        for &netuid in subnets.iter() {
+            if netuid == Self::get_root_netuid()
+                || SubnetMechanism::<T>::get(netuid) == 0
+                || FirstEmissionBlockNumber::<T>::get(netuid).is_none()
+            {
+                continue;
+            }
+            Self::update_moving_price(netuid);
+            updated = updated.saturating_add(1);
+        }
+        Self::update_all_moving_prices_weight(subnets.len() as u64, updated)
+    }
+
+    /// Weight of `update_all_moving_prices` for `checked` subnets, `updated` of which are dynamic.
+    ///
+    /// Each checked subnet reads its mechanism and first emission block. Each updated subnet
+    /// additionally reads its last update block, the halving time, moving alpha, reserves and
+    /// moving price, and writes the moving price and last update block.
+    pub fn update_all_moving_prices_weight(checked: u64, updated: u64) -> Weight {
+        T::DbWeight::get()
+            .reads(checked.saturating_mul(2).saturating_add(1))
+            .saturating_add(
+                T::DbWeight::get()
+                    .reads_writes(updated.saturating_mul(7), updated.saturating_mul(2)),
+            )
     }
 
     /// Retrieves the global global weight as a normalized value between 0 and 1.
@@ -106,12 +932,13 @@ impl<T: Config> Pallet<T> {
         // Step 1: Fetch the global weight from storage
         let stored_weight = TaoWeight::<T>::get();
 
-        // Step 2: Convert the u64 weight to U96F32
-        let weight_fixed = U96F32::saturating_from_num(stored_weight);
+        // Step 2: Normalize the stored weight
+        Self::normalize_tao_weight(stored_weight)
+    }
 
-        // Step 3: Normalize the weight by dividing by u64::MAX
-        // This ensures the result is always between 0 and 1
-        weight_fixed.safe_div(U96F32::saturating_from_num(u64::MAX))
+    /// Normalizes a raw u64 tao weight to a U96F32 between 0 and 1 by dividing it by u64::MAX.
+    pub fn normalize_tao_weight(weight: u64) -> U96F32 {
+        U96F32::saturating_from_num(weight).safe_div(U96F32::saturating_from_num(u64::MAX))
     }
 
     /// Sets the global global weight in storage.
@@ -135,14 +962,28 @@ impl<T: Config> Pallet<T> {
         TaoWeight::<T>::set(weight);
     }
 
+    /// Retrieves the normalized tao weight of a subnet, used for its stake weights and root
+    /// dividends: the `SubnetTaoWeight` override when set, otherwise the global `TaoWeight`.
+    pub fn get_tao_weight_for_subnet(netuid: u16) -> U96F32 {
+        SubnetTaoWeight::<T>::get(netuid)
+            .map(Self::normalize_tao_weight)
+            .unwrap_or_else(Self::get_tao_weight)
+    }
+
+    /// Sets or clears the tao weight override of a subnet.
+    pub fn set_subnet_tao_weight(netuid: u16, weight: Option<u64>) {
+        SubnetTaoWeight::<T>::set(netuid, weight);
+        Self::deposit_event(Event::SubnetTaoWeightSet(netuid, weight));
+    }
+
     /// Calculates the weighted combination of alpha and global tao for a single hotkey onet a subnet.
     ///
     pub fn get_stake_weights_for_hotkey_on_subnet(
         hotkey: &T::AccountId,
         netuid: u16,
     ) -> (I64F64, I64F64, I64F64) {
-        // Retrieve the global tao weight.
-        let tao_weight = I64F64::saturating_from_num(Self::get_tao_weight());
+        // Retrieve the tao weight of the subnet.
+        let tao_weight = I64F64::saturating_from_num(Self::get_tao_weight_for_subnet(netuid));
         log::debug!("tao_weight: {:?}", tao_weight);
 
         // Step 1: Get stake of hotkey (neuron)
@@ -163,11 +1004,70 @@ impl<T: Config> Pallet<T> {
         (total_stake, alpha_stake, tao_stake)
     }
 
+    /// Returns the part of a hotkey's weighted stake on a subnet that comes from its tao stake,
+    /// i.e. `tao_stake * tao_weight`.
+    ///
+    pub fn get_tao_weight_contribution(hotkey: &T::AccountId, netuid: u16) -> I64F64 {
+        let (total_stake, alpha_stake, _) =
+            Self::get_stake_weights_for_hotkey_on_subnet(hotkey, netuid);
+        total_stake.saturating_sub(alpha_stake)
+    }
+
+    /// Returns the raw tao weight at which the weighted stakes of `hotkey_a` and `hotkey_b` on a
+    /// subnet are equal, i.e. the weight above (or below) which A overtakes B.
+    ///
+    /// Solves `alpha_a + w * tao_a = alpha_b + w * tao_b` for `w` in [0, 1] and scales it to the
+    /// u64 representation stored in TaoWeight. Returns None if no weight in that range equalizes
+    /// the stakes, in particular when A outranks B regardless of the weight.
+    ///
+    pub fn breakeven_tao_weight(
+        hotkey_a: &T::AccountId,
+        hotkey_b: &T::AccountId,
+        netuid: u16,
+    ) -> Option<u64> {
+        let (_, alpha_a, tao_a) = Self::get_stake_weights_for_hotkey_on_subnet(hotkey_a, netuid);
+        let (_, alpha_b, tao_b) = Self::get_stake_weights_for_hotkey_on_subnet(hotkey_b, netuid);
+
+        // The alpha gap must be closed by the weighted tao gap.
+        let alpha_gap = alpha_b.saturating_sub(alpha_a);
+        let tao_gap = tao_a.saturating_sub(tao_b);
+        let weight = alpha_gap.checked_div(tao_gap)?;
+        if weight < I64F64::saturating_from_num(0) || weight > I64F64::saturating_from_num(1) {
+            return None;
+        }
+
+        Some(
+            U96F32::saturating_from_num(weight)
+                .saturating_mul(U96F32::saturating_from_num(u64::MAX))
+                .saturating_to_num::<u64>(),
+        )
+    }
+
+    /// Returns the additional alpha a hotkey needs on a subnet for its weighted stake to reach
+    /// `target_weight`.
+    ///
+    /// The tao term of the weighted stake is held fixed, so each unit of alpha raises the weight
+    /// by exactly one. Returns zero if the hotkey is already at or above the target.
+    ///
+    pub fn alpha_needed_for_target_weight(
+        hotkey: &T::AccountId,
+        netuid: u16,
+        target_weight: I64F64,
+    ) -> u64 {
+        let (total_stake, _, _) = Self::get_stake_weights_for_hotkey_on_subnet(hotkey, netuid);
+        target_weight
+            .saturating_sub(total_stake)
+            .max(I64F64::saturating_from_num(0))
+            .saturating_ceil()
+            .saturating_to_num::<u64>()
+    }
+
     /// Calculates the weighted combination of alpha and global tao for hotkeys on a subnet.
     ///
     pub fn get_stake_weights_for_network(netuid: u16) -> (Vec<I64F64>, Vec<I64F64>, Vec<I64F64>) {
-        // Retrieve the global tao weight.
-        let tao_weight: I64F64 = I64F64::saturating_from_num(Self::get_tao_weight());
+        // Retrieve the tao weight of the subnet.
+        let tao_weight: I64F64 =
+            I64F64::saturating_from_num(Self::get_tao_weight_for_subnet(netuid));
         log::debug!("tao_weight: {:?}", tao_weight);
 
         // Step 1: Get subnetwork size
@@ -216,6 +1116,95 @@ impl<T: Config> Pallet<T> {
         (total_stake, alpha_stake, tao_stake)
     }
 
+    /// Calculates the weighted combination of alpha and global tao for hotkeys on a subnet, as
+    /// `get_stake_weights_for_network` does, but with a supplied raw tao weight instead of the
+    /// stored `TaoWeight`.
+    ///
+    pub fn get_stake_weights_with_tao_weight(netuid: u16, tao_weight_override: u64) -> Vec<I64F64> {
+        let tao_weight: I64F64 =
+            I64F64::saturating_from_num(Self::normalize_tao_weight(tao_weight_override));
+        let (_, alpha_stake, tao_stake) = Self::get_stake_weights_for_network(netuid);
+        alpha_stake
+            .iter()
+            .zip(tao_stake.iter())
+            .map(|(alpha_i, tao_i)| alpha_i.saturating_add(tao_i.saturating_mul(tao_weight)))
+            .collect()
+    }
+
+    /// Returns the current total stake weight of every UID on a subnet next to its weight under
+    /// a new raw tao weight, so the rank changes of a `TaoWeight` update can be previewed.
+    ///
+    /// # Returns
+    /// * `Vec<(u16, I64F64, I64F64)>` - The uid, its current weight and its projected weight.
+    ///
+    pub fn simulate_tao_weight_change(
+        netuid: u16,
+        new_tao_weight: u64,
+    ) -> Vec<(u16, I64F64, I64F64)> {
+        let (total_stake, _, _) = Self::get_stake_weights_for_network(netuid);
+        let projected_stake = Self::get_stake_weights_with_tao_weight(netuid, new_tao_weight);
+        (0..Self::get_subnetwork_n(netuid))
+            .zip(total_stake)
+            .zip(projected_stake)
+            .map(|((uid, old), new)| (uid, old, new))
+            .collect()
+    }
+
+    /// Returns the total stake weight of each of the given UIDs on a subnet.
+    ///
+    /// Computes only the requested entries of `get_stake_weights_for_network`, which is cheaper
+    /// for sparse queries. UIDs without a registered hotkey are skipped.
+    ///
+    pub fn get_stake_weights_for_uids(netuid: u16, uids: Vec<u16>) -> Vec<(u16, I64F64)> {
+        uids.into_iter()
+            .filter_map(|uid| {
+                let hotkey = Keys::<T>::try_get(netuid, uid).ok()?;
+                let (total_stake, _, _) =
+                    Self::get_stake_weights_for_hotkey_on_subnet(&hotkey, netuid);
+                Some((uid, total_stake))
+            })
+            .collect()
+    }
+
+    /// Returns the parents and children of a hotkey on a subnet, truncated to the
+    /// `MaxParents` and `MaxChildren` bounds so inheritance walks stay bounded even if
+    /// storage holds more relations than currently allowed.
+    pub fn get_bounded_parents_and_children(
+        hotkey: &T::AccountId,
+        netuid: u16,
+    ) -> (Vec<(u64, T::AccountId)>, Vec<(u64, T::AccountId)>) {
+        let mut parents: Vec<(u64, T::AccountId)> = Self::get_parents(hotkey, netuid);
+        let mut children: Vec<(u64, T::AccountId)> = Self::get_children(hotkey, netuid);
+
+        // A hotkey must never inherit from or delegate to itself.
+        parents.retain(|(_, parent)| parent != hotkey);
+        children.retain(|(_, child)| child != hotkey);
+
+        let max_parents = T::MaxParents::get() as usize;
+        if parents.len() > max_parents {
+            log::warn!(
+                "Hotkey {:?} on subnet {} has {} parents, only the first {} are used",
+                hotkey,
+                netuid,
+                parents.len(),
+                max_parents
+            );
+            parents.truncate(max_parents);
+        }
+        let max_children = T::MaxChildren::get() as usize;
+        if children.len() > max_children {
+            log::warn!(
+                "Hotkey {:?} on subnet {} has {} children, only the first {} are used",
+                hotkey,
+                netuid,
+                children.len(),
+                max_children
+            );
+            children.truncate(max_children);
+        }
+        (parents, children)
+    }
+
     /// Calculates the total inherited stake (alpha) held by a hotkey on a network, considering child/parent relationships.
     ///
     /// This function performs the following steps:
@@ -253,8 +1242,7 @@ impl<T: Config> Pallet<T> {
         let mut tao_from_parents: U96F32 = U96F32::saturating_from_num(0);
 
         // Step 2: Retrieve the lists of parents and children for the hotkey on the subnet.
-        let parents: Vec<(u64, T::AccountId)> = Self::get_parents(hotkey, netuid);
-        let children: Vec<(u64, T::AccountId)> = Self::get_children(hotkey, netuid);
+        let (parents, children) = Self::get_bounded_parents_and_children(hotkey, netuid);
         log::trace!(
             "Parents for hotkey {:?} on subnet {}: {:?}",
             hotkey,
@@ -338,6 +1326,29 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn get_inherited_for_hotkey_on_subnet(hotkey: &T::AccountId, netuid: u16) -> u64 {
+        Self::get_inherited_for_hotkey_with_children(hotkey, netuid, None)
+    }
+
+    /// Previews the inherited alpha of a hotkey on a subnet as if `children` were its children,
+    /// without persisting them. Parents are read from storage as usual.
+    ///
+    /// Comparing the result with `get_inherited_for_hotkey_on_subnet` shows how much of its own
+    /// weight the hotkey gives up by setting those children.
+    pub fn preview_children_impact(
+        hotkey: &T::AccountId,
+        netuid: u16,
+        children: Vec<(u64, T::AccountId)>,
+    ) -> u64 {
+        Self::get_inherited_for_hotkey_with_children(hotkey, netuid, Some(children))
+    }
+
+    /// Calculates the inherited alpha of a hotkey on a subnet, using `children` instead of the
+    /// stored children when given.
+    fn get_inherited_for_hotkey_with_children(
+        hotkey: &T::AccountId,
+        netuid: u16,
+        children: Option<Vec<(u64, T::AccountId)>>,
+    ) -> u64 {
         // Step 1: Retrieve the initial total stake (alpha) for the hotkey on the specified subnet.
         let initial_alpha: U96F32 =
             U96F32::saturating_from_num(Self::get_stake_for_hotkey_on_subnet(hotkey, netuid));
@@ -356,8 +1367,13 @@ impl<T: Config> Pallet<T> {
         let mut alpha_from_parents: U96F32 = U96F32::saturating_from_num(0);
 
         // Step 2: Retrieve the lists of parents and children for the hotkey on the subnet.
-        let parents: Vec<(u64, T::AccountId)> = Self::get_parents(hotkey, netuid);
-        let children: Vec<(u64, T::AccountId)> = Self::get_children(hotkey, netuid);
+        let (parents, stored_children) = Self::get_bounded_parents_and_children(hotkey, netuid);
+        let children = children.map_or(stored_children, |children| {
+            children
+                .into_iter()
+                .filter(|(_, child)| child != hotkey)
+                .collect()
+        });
         log::debug!(
             "Parents for hotkey {:?} on subnet {}: {:?}",
             hotkey,
@@ -564,15 +1580,83 @@ impl<T: Config> Pallet<T> {
         netuid: u16,
         amount: u64,
     ) -> u64 {
+        let had_shares = Self::position_has_shares(hotkey, coldkey, netuid);
         let mut alpha_share_pool = Self::get_alpha_share_pool(hotkey.clone(), netuid);
         // We expect to add a positive amount here.
         let actual_alpha = alpha_share_pool.update_value_for_one(coldkey, amount as i64);
+        Self::update_staker_count(hotkey, coldkey, netuid, had_shares);
 
         // We should return a positive amount, or 0 if the operation failed.
         // e.g. the stake was removed due to precision issues.
         actual_alpha.max(0).unsigned_abs()
     }
 
+    /// Returns true if a coldkey holds alpha shares of a hotkey on a subnet.
+    fn position_has_shares(hotkey: &T::AccountId, coldkey: &T::AccountId, netuid: u16) -> bool {
+        Alpha::<T>::get((hotkey, coldkey, netuid)) != U64F64::saturating_from_num(0)
+    }
+
+    /// Updates `SubnetStakerCount` after the shares of a position changed, given whether the
+    /// position held shares before the change. A fully exited position also drops its
+    /// `PositionFeesPaid` total.
+    ///
+    /// The count is of distinct coldkeys: a coldkey staking to several hotkeys on the subnet is
+    /// counted once.
+    fn update_staker_count(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: u16,
+        had_shares: bool,
+    ) {
+        match (
+            had_shares,
+            Self::position_has_shares(hotkey, coldkey, netuid),
+        ) {
+            (false, true) => Self::add_staker_position(coldkey, netuid),
+            (true, false) => {
+                Self::remove_staker_position(coldkey, netuid);
+                PositionFeesPaid::<T>::remove((coldkey, hotkey, netuid));
+            }
+            _ => {}
+        }
+    }
+
+    /// Records a new position of a coldkey on a subnet in `SubnetColdkeyPositions`, counting the
+    /// coldkey as a new staker if it held no other position there.
+    pub(crate) fn add_staker_position(coldkey: &T::AccountId, netuid: u16) {
+        let positions = SubnetColdkeyPositions::<T>::get(netuid, coldkey);
+        SubnetColdkeyPositions::<T>::insert(netuid, coldkey, positions.saturating_add(1));
+        if positions == 0 {
+            SubnetStakerCount::<T>::mutate(netuid, |count| {
+                *count = count.saturating_add(1);
+            });
+        }
+    }
+
+    /// Records a closed position of a coldkey on a subnet in `SubnetColdkeyPositions`, removing
+    /// the coldkey from the staker count once it holds no position there.
+    pub(crate) fn remove_staker_position(coldkey: &T::AccountId, netuid: u16) {
+        let positions = SubnetColdkeyPositions::<T>::get(netuid, coldkey);
+        if positions > 1 {
+            SubnetColdkeyPositions::<T>::insert(netuid, coldkey, positions.saturating_sub(1));
+        } else if positions == 1 {
+            SubnetColdkeyPositions::<T>::remove(netuid, coldkey);
+            SubnetStakerCount::<T>::mutate(netuid, |count| {
+                *count = count.saturating_sub(1);
+            });
+        }
+    }
+
+    /// Returns true if a subnet accepts a stake of `amount` TAO: either the stake is not above
+    /// `LargeStakeThreshold`, or the subnet has reached its `MinStakersForLargeStake` positions.
+    pub fn large_stake_allowed(netuid: u16, amount: u64) -> bool {
+        let Some(min_stakers) = MinStakersForLargeStake::<T>::get(netuid) else {
+            return true;
+        };
+        amount <= LargeStakeThreshold::<T>::get()
+            || SubnetStakerCount::<T>::get(netuid) >= min_stakers
+    }
+
     pub fn try_increase_stake_for_hotkey_and_coldkey_on_subnet(
         hotkey: &T::AccountId,
         netuid: u16,
@@ -582,6 +1666,16 @@ impl<T: Config> Pallet<T> {
         alpha_share_pool.sim_update_value_for_one(amount as i64)
     }
 
+    /// Returns true if staking `amount` alpha to a hotkey on a subnet mints at least the
+    /// subnet's `MinShareIncrement` shares.
+    pub fn meets_min_share_increment(hotkey: &T::AccountId, netuid: u16, amount: u64) -> bool {
+        let mut alpha_share_pool = Self::get_alpha_share_pool(hotkey.clone(), netuid);
+        alpha_share_pool.sim_update_value_for_one_with_min_shares(
+            amount as i64,
+            MinShareIncrement::<T>::get(netuid),
+        )
+    }
+
     /// Sell shares in the hotkey on a given subnet
     ///
     /// The function updates share totals given current prices.
@@ -598,6 +1692,7 @@ impl<T: Config> Pallet<T> {
         netuid: u16,
         amount: u64,
     ) -> u64 {
+        let had_shares = Self::position_has_shares(hotkey, coldkey, netuid);
         let mut alpha_share_pool = Self::get_alpha_share_pool(hotkey.clone(), netuid);
 
         // We expect a negative value here
@@ -609,6 +1704,8 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        Self::update_staker_count(hotkey, coldkey, netuid, had_shares);
+
         // Get the negation of the removed alpha, and clamp at 0.
         // This ensures we return a positive value, but only if
         // `actual_alpha` was negative (i.e. a decrease in stake).
@@ -618,7 +1715,7 @@ impl<T: Config> Pallet<T> {
     /// Calculates Some(Alpha) returned from pool by staking operation
     /// if liquidity allows that. If not, returns None.
     ///
-    /// If new alpha_reserve is about to drop below DefaultMinimumPoolLiquidity,
+    /// If new alpha_reserve is about to drop below the subnet's minimum pool liquidity,
     /// then don't do it.
     ///
     pub fn sim_swap_tao_for_alpha(netuid: u16, tao: u64) -> Option<u64> {
@@ -639,7 +1736,7 @@ impl<T: Config> Pallet<T> {
 
             // Step 3.a.3: Calculate alpha staked using the constant product formula
             // alpha_stake_recieved = current_alpha - (k / (current_tao + new_tao))
-            if new_alpha_reserves >= DefaultMinimumPoolLiquidity::<T>::get() {
+            if new_alpha_reserves >= Self::get_min_pool_liquidity(netuid) {
                 Some(
                     alpha_reserves
                         .saturating_sub(new_alpha_reserves)
@@ -654,43 +1751,174 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Returns the smallest TAO amount for which `sim_swap_tao_for_alpha` returns nonzero alpha
+    /// at the current reserves, or `u64::MAX` if no amount does.
+    pub fn min_tao_for_nonzero_alpha(netuid: u16) -> u64 {
+        // Amounts beyond the pool liquidity would also produce alpha, which keeps the search
+        // monotonic.
+        let produces_alpha =
+            |tao: u64| Self::sim_swap_tao_for_alpha(netuid, tao).is_none_or(|alpha| alpha > 0);
+
+        let mut low: u64 = 1;
+        let mut high: u64 = u64::MAX;
+        while low < high {
+            let mid = low.saturating_add(high.saturating_sub(low).safe_div(2));
+            if produces_alpha(mid) {
+                high = mid;
+            } else {
+                low = mid.saturating_add(1);
+            }
+        }
+
+        if Self::sim_swap_tao_for_alpha(netuid, low).is_some_and(|alpha| alpha > 0) {
+            low
+        } else {
+            u64::MAX
+        }
+    }
+
+    /// Returns the minimum reserve a swap may leave in the subnet's pool: the subnet's
+    /// MinPoolLiquidityOverride if set, otherwise DefaultMinimumPoolLiquidity.
+    ///
+    pub fn get_min_pool_liquidity(netuid: u16) -> I96F32 {
+        MinPoolLiquidityOverride::<T>::get(netuid)
+            .map(I96F32::saturating_from_num)
+            .unwrap_or_else(DefaultMinimumPoolLiquidity::<T>::get)
+    }
+
     /// Calculates Some(Tao) returned from pool by unstaking operation
     /// if liquidity allows that. If not, returns None.
     ///
-    /// If new tao_reserve is about to drop below DefaultMinimumPoolLiquidity,
-    /// then don't do it.
+    /// If new tao_reserve is about to drop below the subnet's minimum pool liquidity,
+    /// then don't do it.
+    ///
+    pub fn sim_swap_alpha_for_tao(netuid: u16, alpha: u64) -> Option<u64> {
+        // Step 1: Get the mechanism type for the subnet (0 for Stable, 1 for Dynamic)
+        let mechanism_id: u16 = SubnetMechanism::<T>::get(netuid);
+        // Step 2: Swap alpha and attain tao
+        if mechanism_id == 1 {
+            // Step 3.a.1: Dynamic mechanism calculations
+            let tao_reserves: U110F18 = U110F18::saturating_from_num(SubnetTAO::<T>::get(netuid));
+            let alpha_reserves: U110F18 =
+                U110F18::saturating_from_num(SubnetAlphaIn::<T>::get(netuid));
+            // Step 3.a.2: Compute constant product k = alpha * tao
+            let k: U110F18 = alpha_reserves.saturating_mul(tao_reserves);
+
+            // Calculate new tao reserve
+            let new_tao_reserves: U110F18 = k
+                .checked_div(alpha_reserves.saturating_add(U110F18::saturating_from_num(alpha)))
+                .unwrap_or(U110F18::saturating_from_num(0));
+
+            // Step 3.a.3: Calculate alpha staked using the constant product formula
+            // tao_recieved = tao_reserves - (k / (alpha_reserves + new_tao))
+            if new_tao_reserves >= Self::get_min_pool_liquidity(netuid) {
+                Some(
+                    tao_reserves
+                        .saturating_sub(new_tao_reserves)
+                        .saturating_to_num::<u64>(),
+                )
+            } else {
+                None
+            }
+        } else {
+            // Step 3.b.1: Stable mechanism, just return the value 1:1
+            Some(alpha)
+        }
+    }
+
+    /// Calculates Some(Alpha) received on the destination subnet for unstaking `alpha` on the
+    /// origin subnet and staking the resulting TAO, if liquidity allows that on both legs. If
+    /// not, returns None.
+    ///
+    pub fn sim_swap_alpha_for_alpha(
+        origin_netuid: u16,
+        destination_netuid: u16,
+        alpha: u64,
+    ) -> Option<u64> {
+        let tao = Self::sim_swap_alpha_for_tao(origin_netuid, alpha)?;
+        Self::sim_swap_tao_for_alpha(destination_netuid, tao)
+    }
+
+    /// Calculates Some(Tao) that needs to be staked to receive exactly `alpha` from the pool
+    /// if liquidity allows that. If not, returns None.
+    ///
+    /// This inverts the formula of `sim_swap_tao_for_alpha`, rounding the TAO up. If new
+    /// alpha_reserve is about to drop below the subnet's minimum pool liquidity, returns None.
+    ///
+    pub fn tao_for_exact_alpha(netuid: u16, alpha: u64) -> Option<u64> {
+        // Step 1: Get the mechanism type for the subnet (0 for Stable, 1 for Dynamic)
+        let mechanism_id: u16 = SubnetMechanism::<T>::get(netuid);
+        if mechanism_id == 1 {
+            // Step 2.a.1: Dynamic mechanism calculations
+            let tao_reserves: U110F18 = U110F18::saturating_from_num(SubnetTAO::<T>::get(netuid));
+            let alpha_reserves: U110F18 =
+                U110F18::saturating_from_num(SubnetAlphaIn::<T>::get(netuid));
+            let new_alpha_reserves =
+                alpha_reserves.checked_sub(U110F18::saturating_from_num(alpha))?;
+            if new_alpha_reserves < Self::get_min_pool_liquidity(netuid) {
+                return None;
+            }
+
+            // Step 2.a.2: tao_needed = k / (current_alpha - alpha) - current_tao, rounded up
+            let k: U110F18 = alpha_reserves.saturating_mul(tao_reserves);
+            let tao: U110F18 = k.safe_div(new_alpha_reserves).saturating_sub(tao_reserves);
+            u64::try_from(tao.saturating_to_num::<u128>().saturating_add(1)).ok()
+        } else {
+            // Step 2.b.1: Stable mechanism, just return the value 1:1
+            Some(alpha)
+        }
+    }
+
+    /// Calculates Some(Alpha) that needs to be unstaked to receive exactly `tao` from the pool,
+    /// before fees, if liquidity allows that. If not, returns None.
+    ///
+    /// This inverts the formula of `sim_swap_alpha_for_tao`, rounding the alpha up. If new
+    /// tao_reserve is about to drop below the subnet's minimum pool liquidity, returns None.
+    ///
+    pub fn alpha_needed_for_tao(netuid: u16, tao: u64) -> Option<u64> {
+        // Step 1: Get the mechanism type for the subnet (0 for Stable, 1 for Dynamic)
+        let mechanism_id: u16 = SubnetMechanism::<T>::get(netuid);
+        if mechanism_id == 1 {
+            // Step 2.a.1: Dynamic mechanism calculations
+            let tao_reserves: U110F18 = U110F18::saturating_from_num(SubnetTAO::<T>::get(netuid));
+            let alpha_reserves: U110F18 =
+                U110F18::saturating_from_num(SubnetAlphaIn::<T>::get(netuid));
+            let new_tao_reserves = tao_reserves.checked_sub(U110F18::saturating_from_num(tao))?;
+            if new_tao_reserves < Self::get_min_pool_liquidity(netuid) {
+                return None;
+            }
+
+            // Step 2.a.2: alpha_needed = k / (current_tao - tao) - current_alpha, rounded up
+            let k: U110F18 = alpha_reserves.saturating_mul(tao_reserves);
+            let alpha: U110F18 = k.safe_div(new_tao_reserves).saturating_sub(alpha_reserves);
+            u64::try_from(alpha.saturating_to_num::<u128>().saturating_add(1)).ok()
+        } else {
+            // Step 2.b.1: Stable mechanism, just return the value 1:1
+            Some(tao)
+        }
+    }
+
+    /// Calculates the alpha that a TAO injection would buy at the current reserves.
+    ///
+    /// Uses the same constant product formula as `sim_swap_tao_for_alpha`, but is not gated by
+    /// DefaultMinimumPoolLiquidity because it is only a projection for emission modeling.
     ///
-    pub fn sim_swap_alpha_for_tao(netuid: u16, alpha: u64) -> Option<u64> {
-        // Step 1: Get the mechanism type for the subnet (0 for Stable, 1 for Dynamic)
-        let mechanism_id: u16 = SubnetMechanism::<T>::get(netuid);
-        // Step 2: Swap alpha and attain tao
-        if mechanism_id == 1 {
-            // Step 3.a.1: Dynamic mechanism calculations
+    pub fn alpha_for_tao_injection(netuid: u16, tao: u64) -> u64 {
+        if SubnetMechanism::<T>::get(netuid) == 1 {
             let tao_reserves: U110F18 = U110F18::saturating_from_num(SubnetTAO::<T>::get(netuid));
             let alpha_reserves: U110F18 =
                 U110F18::saturating_from_num(SubnetAlphaIn::<T>::get(netuid));
-            // Step 3.a.2: Compute constant product k = alpha * tao
             let k: U110F18 = alpha_reserves.saturating_mul(tao_reserves);
 
-            // Calculate new tao reserve
-            let new_tao_reserves: U110F18 = k
-                .checked_div(alpha_reserves.saturating_add(U110F18::saturating_from_num(alpha)))
-                .unwrap_or(U110F18::saturating_from_num(0));
-
-            // Step 3.a.3: Calculate alpha staked using the constant product formula
-            // tao_recieved = tao_reserves - (k / (alpha_reserves + new_tao))
-            if new_tao_reserves >= DefaultMinimumPoolLiquidity::<T>::get() {
-                Some(
-                    tao_reserves
-                        .saturating_sub(new_tao_reserves)
-                        .saturating_to_num::<u64>(),
-                )
-            } else {
-                None
-            }
+            // alpha_bought = current_alpha - (k / (current_tao + injected_tao))
+            let new_alpha_reserves: U110F18 =
+                k.safe_div(tao_reserves.saturating_add(U110F18::saturating_from_num(tao)));
+            alpha_reserves
+                .saturating_sub(new_alpha_reserves)
+                .saturating_to_num::<u64>()
         } else {
-            // Step 3.b.1: Stable mechanism, just return the value 1:1
-            Some(alpha)
+            // Stable mechanism, 1:1
+            tao
         }
     }
 
@@ -698,7 +1926,11 @@ impl<T: Config> Pallet<T> {
     ///
     /// Updates TaoIn, AlphaIn, and AlphaOut
     pub fn swap_tao_for_alpha(netuid: u16, tao: u64) -> u64 {
-        if let Some(alpha) = Self::sim_swap_tao_for_alpha(netuid, tao) {
+        if let Some(alpha) = Self::sim_swap_tao_for_alpha(netuid, tao).filter(|alpha| {
+            Self::reserve_remains_positive(netuid, SubnetAlphaIn::<T>::get(netuid), *alpha)
+        }) {
+            #[cfg(debug_assertions)]
+            let alpha_issuance = Self::get_alpha_issuance(netuid);
             // Step 4. Decrease Alpha reserves.
             SubnetAlphaIn::<T>::mutate(netuid, |total| {
                 *total = total.saturating_sub(alpha);
@@ -719,18 +1951,47 @@ impl<T: Config> Pallet<T> {
             SubnetVolume::<T>::mutate(netuid, |total| {
                 *total = total.saturating_add(tao.into());
             });
-            // Step 9. Return the alpha received.
+            // Step 9. Report if the swap changed the alpha issuance, in debug builds only.
+            #[cfg(debug_assertions)]
+            Self::report_alpha_issuance_drift(netuid, alpha_issuance);
+            // Step 10. Publish the post-swap reserves.
+            Self::deposit_reserves_changed(netuid);
+            // Step 11. Return the alpha received.
             alpha
         } else {
             0
         }
     }
 
+    /// Reverts the reserve changes of a `swap_tao_for_alpha` of `tao` that returned `alpha`.
+    fn revert_swap_tao_for_alpha(netuid: u16, tao: u64, alpha: u64) {
+        SubnetAlphaIn::<T>::mutate(netuid, |total| {
+            *total = total.saturating_add(alpha);
+        });
+        SubnetAlphaOut::<T>::mutate(netuid, |total| {
+            *total = total.saturating_sub(alpha);
+        });
+        SubnetTAO::<T>::mutate(netuid, |total| {
+            *total = total.saturating_sub(tao);
+        });
+        TotalStake::<T>::mutate(|total| {
+            *total = total.saturating_sub(tao);
+        });
+        SubnetVolume::<T>::mutate(netuid, |total| {
+            *total = total.saturating_sub(tao.into());
+        });
+        Self::deposit_reserves_changed(netuid);
+    }
+
     /// Swaps a subnet's Alpba token for TAO.
     ///
     /// Updates TaoIn, AlphaIn, and AlphaOut
     pub fn swap_alpha_for_tao(netuid: u16, alpha: u64) -> u64 {
-        if let Some(tao) = Self::sim_swap_alpha_for_tao(netuid, alpha) {
+        if let Some(tao) = Self::sim_swap_alpha_for_tao(netuid, alpha)
+            .filter(|tao| Self::reserve_remains_positive(netuid, SubnetTAO::<T>::get(netuid), *tao))
+        {
+            #[cfg(debug_assertions)]
+            let alpha_issuance = Self::get_alpha_issuance(netuid);
             // Step 4: Increase Alpha reserves.
             SubnetAlphaIn::<T>::mutate(netuid, |total| {
                 *total = total.saturating_add(alpha);
@@ -751,16 +2012,271 @@ impl<T: Config> Pallet<T> {
             SubnetVolume::<T>::mutate(netuid, |total| {
                 *total = total.saturating_add(tao.into());
             });
-            // Step 9. Return the tao received.
+            // Step 9. Report if the swap changed the alpha issuance, in debug builds only.
+            #[cfg(debug_assertions)]
+            Self::report_alpha_issuance_drift(netuid, alpha_issuance);
+            // Step 10. Publish the post-swap reserves.
+            Self::deposit_reserves_changed(netuid);
+            // Step 11. Return the tao received.
             tao
         } else {
             0
         }
     }
 
+    /// Returns true if the pool of a subnet can currently absorb a stake of `tao`, i.e. the swap
+    /// would not drop the alpha reserve below the minimum pool liquidity.
+    ///
+    /// Stable subnets are not pools and can always absorb stake.
+    pub fn can_absorb_stake(netuid: u16, tao: u64) -> bool {
+        SubnetMechanism::<T>::get(netuid) == 0
+            || Self::sim_swap_tao_for_alpha(netuid, tao).is_some_and(|alpha| {
+                Self::reserve_remains_positive(netuid, SubnetAlphaIn::<T>::get(netuid), alpha)
+            })
+    }
+
+    /// Returns true if taking `amount` out of a pool reserve leaves it strictly positive.
+    ///
+    /// This hardens the minimum liquidity check of the dynamic mechanisms against degenerate
+    /// pools with a zero constant product. Stable subnets are not pools and always pass.
+    pub fn reserve_remains_positive(netuid: u16, reserve: u64, amount: u64) -> bool {
+        SubnetMechanism::<T>::get(netuid) == 0 || reserve > amount
+    }
+
+    /// Emits the current reserves of a subnet so off-chain systems can mirror the pool.
+    fn deposit_reserves_changed(netuid: u16) {
+        Self::deposit_event(Event::ReservesChanged(
+            netuid,
+            SubnetTAO::<T>::get(netuid),
+            SubnetAlphaIn::<T>::get(netuid),
+            SubnetAlphaOut::<T>::get(netuid),
+        ));
+    }
+
+    /// Returns the TAO and alpha reserves of a subnet if swap auditing is enabled.
+    pub(crate) fn get_audited_reserves(netuid: u16) -> Option<(u64, u64)> {
+        SwapAuditEnabled::<T>::get()
+            .then(|| (SubnetTAO::<T>::get(netuid), SubnetAlphaIn::<T>::get(netuid)))
+    }
+
+    /// Records a swap in `SwapAudit` under the current block, given the reserves read by
+    /// `get_audited_reserves` before the swap. Does nothing if auditing was disabled or the
+    /// swap did not execute.
+    pub(crate) fn record_swap(
+        netuid: u16,
+        is_buy: bool,
+        reserves_before: Option<(u64, u64)>,
+        amount_in: u64,
+        amount_out: u64,
+        fee: u64,
+    ) {
+        let Some((tao_reserve_before, alpha_reserve_before)) = reserves_before else {
+            return;
+        };
+        if amount_out == 0 {
+            return;
+        }
+        let block = Self::get_current_block_as_u64();
+        let index = SwapAuditCount::<T>::get(block);
+        SwapAudit::<T>::insert(
+            block,
+            index,
+            SwapRecord {
+                netuid,
+                is_buy,
+                tao_reserve_before,
+                alpha_reserve_before,
+                tao_reserve_after: SubnetTAO::<T>::get(netuid),
+                alpha_reserve_after: SubnetAlphaIn::<T>::get(netuid),
+                amount_in,
+                amount_out,
+                fee,
+            },
+        );
+        SwapAuditCount::<T>::insert(block, index.saturating_add(1));
+    }
+
+    /// Adds a staking fee charged to a position to its `PositionFeesPaid` total.
+    ///
+    /// Nothing is recorded once the position holds no shares, since the total of a fully exited
+    /// position is removed in `update_staker_count`.
+    pub(crate) fn record_position_fee(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        netuid: u16,
+        fee: u64,
+    ) {
+        if fee == 0 || !Self::position_has_shares(hotkey, coldkey, netuid) {
+            return;
+        }
+        PositionFeesPaid::<T>::mutate((coldkey, hotkey, netuid), |fees| {
+            *fees = fees.saturating_add(fee);
+        });
+    }
+
+    /// Returns the total staking fees in TAO charged to a coldkey-hotkey position on a subnet,
+    /// across both stakes and unstakes.
+    pub fn cumulative_fees_paid(coldkey: &T::AccountId, hotkey: &T::AccountId, netuid: u16) -> u64 {
+        PositionFeesPaid::<T>::get((coldkey, hotkey, netuid))
+    }
+
+    /// Adds `tao` to the stake flow counter of the current interval, positive for TAO staked
+    /// into the subnet and negative for TAO unstaked out of it.
+    ///
+    /// The counter of the ring slot is reset when it still holds an older interval.
+    fn record_stake_flow(netuid: u16, tao: u64, is_inflow: bool) {
+        let tao = i64::try_from(tao).unwrap_or(i64::MAX);
+        let delta = if is_inflow { tao } else { tao.saturating_neg() };
+        let interval = Self::get_current_block_as_u64().safe_div(STAKE_FLOW_INTERVAL);
+        let slot = interval
+            .checked_rem(STAKE_FLOW_INTERVALS)
+            .unwrap_or_default();
+        StakeFlow::<T>::mutate(netuid, slot, |(slot_interval, flow)| {
+            if *slot_interval != interval {
+                *slot_interval = interval;
+                *flow = 0;
+            }
+            *flow = flow.saturating_add(delta);
+        });
+    }
+
+    /// Returns the net TAO staked into a subnet over the last `window_blocks` blocks: TAO
+    /// staked in minus TAO unstaked out. Positive means net inflow.
+    ///
+    /// The flow is tracked per interval of `STAKE_FLOW_INTERVAL` blocks, so the window is
+    /// rounded up to whole intervals (including the current one) and capped at
+    /// `STAKE_FLOW_INTERVALS` intervals.
+    pub fn get_net_stake_flow(netuid: u16, window_blocks: u64) -> i64 {
+        if window_blocks == 0 {
+            return 0;
+        }
+        let current_interval = Self::get_current_block_as_u64().safe_div(STAKE_FLOW_INTERVAL);
+        let intervals = window_blocks
+            .div_ceil(STAKE_FLOW_INTERVAL)
+            .min(STAKE_FLOW_INTERVALS);
+        let first_interval = current_interval.saturating_add(1).saturating_sub(intervals);
+
+        StakeFlow::<T>::iter_prefix(netuid)
+            .filter(|(_, (interval, _))| {
+                *interval >= first_interval && *interval <= current_interval
+            })
+            .fold(0i64, |acc, (_, (_, flow))| acc.saturating_add(flow))
+    }
+
+    /// Removes the swap audit records of the blocks that fell out of `SwapAuditRetention`.
+    ///
+    /// Prunes from `SwapAuditPrunedUntil` up to the retention cutoff, so lowering the retention
+    /// does not leak the blocks in between. Each call walks at most `SWAP_AUDIT_PRUNE_BLOCKS`
+    /// blocks and removes at most `SWAP_AUDIT_PRUNE_RECORDS` records, resuming where it stopped
+    /// on the next block. Returns the weight consumed.
+    pub fn prune_swap_audit(block_number: u64) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let Some(cutoff) = block_number.checked_sub(SwapAuditRetention::<T>::get()) else {
+            return weight;
+        };
+
+        // Blocks before the first cutoff were pruned one by one as they expired.
+        weight.saturating_accrue(T::DbWeight::get().reads(1));
+        let mut next = SwapAuditPrunedUntil::<T>::get();
+        if next == 0 {
+            next = cutoff;
+        }
+
+        let mut blocks_left = SWAP_AUDIT_PRUNE_BLOCKS;
+        let mut records_left = SWAP_AUDIT_PRUNE_RECORDS;
+        while next <= cutoff && blocks_left > 0 {
+            blocks_left = blocks_left.saturating_sub(1);
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            let count = SwapAuditCount::<T>::get(next);
+            if count > 0 {
+                if records_left == 0 {
+                    break;
+                }
+                let removed = SwapAudit::<T>::clear_prefix(next, records_left, None).unique;
+                records_left = records_left.saturating_sub(removed);
+                weight.saturating_accrue(
+                    T::DbWeight::get().writes(u64::from(removed).saturating_add(1)),
+                );
+                if removed < count {
+                    // Out of budget, finish this block on the next call.
+                    SwapAuditCount::<T>::insert(next, count.saturating_sub(removed));
+                    break;
+                }
+                SwapAuditCount::<T>::remove(next);
+            }
+            next = next.saturating_add(1);
+        }
+
+        SwapAuditPrunedUntil::<T>::put(next);
+        weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Returns the audit record of the `index`-th swap of a block, if it was retained.
+    pub fn get_swap_record(block: u64, index: u32) -> Option<SwapRecord> {
+        SwapAudit::<T>::get(block, index)
+    }
+
+    /// Logs an error if the alpha issuance of a subnet differs from the expected value.
+    ///
+    /// Swaps only move alpha between `SubnetAlphaIn` and `SubnetAlphaOut`, so the issuance must
+    /// be the same before and after. A difference means one of the reserves saturated.
+    ///
+    /// Only compiled in debug builds, so the runtime does not pay the extra reads on every swap.
+    #[cfg(debug_assertions)]
+    fn report_alpha_issuance_drift(netuid: u16, expected_issuance: u64) {
+        let alpha_issuance = Self::get_alpha_issuance(netuid);
+        if alpha_issuance != expected_issuance {
+            log::error!(
+                "Alpha issuance drift on netuid {:?}: expected {:?}, got {:?}",
+                netuid,
+                expected_issuance,
+                alpha_issuance
+            );
+        }
+    }
+
+    /// Checks the alpha accounting invariant of a subnet: the alpha held by hotkeys on the
+    /// subnet matches the outstanding alpha (`SubnetAlphaOut`) within the rounding tolerance.
+    ///
+    /// Emitted alpha not yet distributed (`PendingEmission` and `PendingOwnerCut`) is
+    /// outstanding but not held. The tolerance is one rao per hotkey, the rounding of its
+    /// share pool. Also fails if `SubnetAlphaIn` + `SubnetAlphaOut` overflows.
+    ///
+    /// Only compiled for tests and try-runtime, as it iterates over all `TotalHotkeyAlpha`
+    /// entries.
+    ///
+    /// # Arguments
+    /// * `netuid` - The unique identifier of the subnet.
+    ///
+    /// # Returns
+    /// * `bool` - True if the invariant holds.
+    #[cfg(any(test, feature = "try-runtime"))]
+    pub(crate) fn check_alpha_conservation(netuid: u16) -> bool {
+        let alpha_in = SubnetAlphaIn::<T>::get(netuid);
+        let alpha_out = SubnetAlphaOut::<T>::get(netuid);
+        if alpha_in.checked_add(alpha_out).is_none() {
+            return false;
+        }
+
+        let (hotkeys, held_alpha) = TotalHotkeyAlpha::<T>::iter()
+            .filter(|(_, hotkey_netuid, _)| *hotkey_netuid == netuid)
+            .fold((0u128, 0u128), |(hotkeys, held), (_, _, alpha)| {
+                (
+                    hotkeys.saturating_add(1),
+                    held.saturating_add(u128::from(alpha)),
+                )
+            });
+        let outstanding_alpha = u128::from(alpha_out)
+            .saturating_sub(u128::from(PendingEmission::<T>::get(netuid)))
+            .saturating_sub(u128::from(PendingOwnerCut::<T>::get(netuid)));
+        held_alpha.abs_diff(outstanding_alpha) <= hotkeys
+    }
+
     /// Unstakes alpha from a subnet for a given hotkey and coldkey pair.
     ///
     /// We update the pools associated with a subnet as well as update hotkey alpha shares.
+    /// Callers must reject fees that consume all of the unstaked TAO, see
+    /// `fee_exceeds_unstake_amount`.
     pub fn unstake_from_subnet(
         hotkey: &T::AccountId,
         coldkey: &T::AccountId,
@@ -769,29 +2285,43 @@ impl<T: Config> Pallet<T> {
         fee: u64,
     ) -> u64 {
         // Step 1: Decrease alpha on subneet
+        let hotkey_alpha_before = TotalHotkeyAlpha::<T>::get(hotkey, netuid);
         let actual_alpha_decrease =
             Self::decrease_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid, alpha);
 
+        // Step 1.1: Signal when the hotkey has fully exited the subnet
+        if hotkey_alpha_before > 0 && TotalHotkeyAlpha::<T>::get(hotkey, netuid) == 0 {
+            Self::deposit_event(Event::HotkeyExitedSubnet(hotkey.clone(), netuid));
+        }
+
         // Step 2: Swap the alpha for TAO.
+        let reserves_before = Self::get_audited_reserves(netuid);
         let tao: u64 = Self::swap_alpha_for_tao(netuid, actual_alpha_decrease);
 
-        // Step 3: Update StakingHotkeys if the hotkey's total alpha, across all subnets, is zero
-        // TODO const: fix.
-        // if Self::get_stake(hotkey, coldkey) == 0 {
-        //     StakingHotkeys::<T>::mutate(coldkey, |hotkeys| {
-        //         hotkeys.retain(|k| k != hotkey);
-        //     });
-        // }
+        // Step 3: Update StakingHotkeys if the hotkey's total alpha, across all subnets, is zero.
+        // The share pool removes emptied positions from Alpha, so at most one entry is read.
+        if !Self::position_has_shares(hotkey, coldkey, netuid)
+            && Alpha::<T>::iter_prefix((hotkey, coldkey)).next().is_none()
+        {
+            StakingHotkeys::<T>::mutate(coldkey, |hotkeys| {
+                hotkeys.retain(|k| k != hotkey);
+            });
+        }
 
         // Step 4. Reduce tao amount by staking fee and credit this fee to SubnetTAO
         let tao_unstaked = tao.saturating_sub(fee);
         let actual_fee = tao.saturating_sub(tao_unstaked);
-        SubnetTAO::<T>::mutate(netuid, |total| {
-            *total = total.saturating_add(actual_fee);
-        });
-        TotalStake::<T>::mutate(|total| {
-            *total = total.saturating_add(actual_fee);
-        });
+        Self::record_swap(
+            netuid,
+            false,
+            reserves_before,
+            actual_alpha_decrease,
+            tao,
+            actual_fee,
+        );
+        Self::record_stake_flow(netuid, tao, false);
+        Self::record_position_fee(coldkey, hotkey, netuid, actual_fee);
+        Self::credit_fee(hotkey, netuid, actual_fee);
         LastColdkeyHotkeyStakeBlock::<T>::insert(coldkey, hotkey, Self::get_current_block_as_u64());
 
         // Step 5. Deposit and log the unstaking event.
@@ -817,16 +2347,118 @@ impl<T: Config> Pallet<T> {
         tao_unstaked
     }
 
+    /// Burns the `FeeBurnBps` share of a staking fee and adds the rest to the subnet TAO
+    /// reserve and the total stake.
+    ///
+    /// If `FeeRedistributionEnabled` is set for the subnet, the rest is instead swapped for
+    /// alpha and distributed to the stakers of `hotkey` through its share pool. It falls back to
+    /// the reserves when the hotkey has no stake or the swap fails.
+    pub(crate) fn credit_fee(hotkey: &T::AccountId, netuid: u16, fee: u64) {
+        let burned = Self::get_fee_burn_amount(netuid, fee);
+        if burned > 0 {
+            Self::burn_tokens(burned);
+            Self::deposit_event(Event::StakingFeeBurned(netuid, burned));
+        }
+
+        let credited = fee.saturating_sub(burned);
+        if credited == 0 {
+            return;
+        }
+        if FeeRedistributionEnabled::<T>::get(netuid)
+            && TotalHotkeyAlpha::<T>::get(hotkey, netuid) > 0
+        {
+            let alpha = Self::swap_tao_for_alpha(netuid, credited);
+            if alpha > 0 {
+                Self::increase_stake_for_hotkey_on_subnet(hotkey, netuid, alpha);
+                Self::deposit_event(Event::FeeRedistributedToStakers(
+                    hotkey.clone(),
+                    netuid,
+                    alpha,
+                ));
+                return;
+            }
+        }
+        SubnetTAO::<T>::mutate(netuid, |total| {
+            *total = total.saturating_add(credited);
+        });
+        TotalStake::<T>::mutate(|total| {
+            *total = total.saturating_add(credited);
+        });
+        Self::deposit_event(Event::FeeCreditedToReserves(netuid, credited));
+    }
+
+    /// Returns the part of a staking fee that is burned on a subnet.
+    pub fn get_fee_burn_amount(netuid: u16, fee: u64) -> u64 {
+        let burn_bps = FeeBurnBps::<T>::get(netuid).min(10_000);
+        (fee as u128)
+            .saturating_mul(burn_bps as u128)
+            .checked_div(10_000)
+            .unwrap_or(0) as u64
+    }
+
+    /// Returns true if `fee` takes more than `MaxFeeShareBps` of a trade worth `tao_value`.
+    ///
+    /// Always false while `MaxFeeShareBps` is zero, so the fee floor is charged on any trade.
+    pub fn fee_is_disproportionate(fee: u64, tao_value: u64) -> bool {
+        let max_share_bps = MaxFeeShareBps::<T>::get();
+        if max_share_bps == 0 {
+            return false;
+        }
+        u128::from(fee).saturating_mul(10_000)
+            > u128::from(tao_value).saturating_mul(u128::from(max_share_bps))
+    }
+
+    /// Returns true if a non-zero fee would consume all of the TAO received for unstaking alpha.
+    pub fn fee_exceeds_unstake_amount(netuid: u16, alpha: u64, fee: u64) -> bool {
+        fee > 0 && Self::sim_swap_alpha_for_tao(netuid, alpha).is_some_and(|tao| tao <= fee)
+    }
+
+    /// Returns the smallest alpha that passes the minimum stake check of `validate_remove_stake`,
+    /// i.e. whose `sim_swap_alpha_for_tao` exceeds `DefaultMinStake`.
+    ///
+    /// The TAO received grows with the alpha sold until the pool runs out of liquidity, so the
+    /// amount is found by a binary search over the alpha range. Returns zero if no amount of
+    /// alpha can be unstaked from the pool.
+    pub fn min_unstakeable_alpha(netuid: u16) -> u64 {
+        let min_stake = DefaultMinStake::<T>::get();
+        // Amounts the pool cannot absorb are treated as passing so that the search stays
+        // monotonic; the result is checked against the pool below.
+        let passes = |alpha: u64| {
+            Self::sim_swap_alpha_for_tao(netuid, alpha).is_none_or(|tao| tao > min_stake)
+        };
+
+        let mut low: u64 = 1;
+        let mut high: u64 = u64::MAX;
+        while low < high {
+            let mid = low.saturating_add(high.saturating_sub(low).safe_div(2));
+            if passes(mid) {
+                high = mid;
+            } else {
+                low = mid.saturating_add(1);
+            }
+        }
+
+        if Self::sim_swap_alpha_for_tao(netuid, low).is_some_and(|tao| tao > min_stake) {
+            low
+        } else {
+            0
+        }
+    }
+
     /// Stakes TAO into a subnet for a given hotkey and coldkey pair.
     ///
     /// We update the pools associated with a subnet as well as update hotkey alpha shares.
+    ///
+    /// Fails with `StakeNotCredited` if the swapped alpha cannot be credited to the hotkey, after
+    /// undoing the swap. The caller must propagate the error so the TAO withdrawn for the stake is
+    /// rolled back with the rest of the transaction.
     pub(crate) fn stake_into_subnet(
         hotkey: &T::AccountId,
         coldkey: &T::AccountId,
         netuid: u16,
         tao: u64,
         fee: u64,
-    ) -> u64 {
+    ) -> Result<u64, Error<T>> {
         // Step 1. Reduce tao amount by staking fee and credit this fee to SubnetTAO
         // At this point tao was already withdrawn from the user balance and is considered
         // available
@@ -834,6 +2466,8 @@ impl<T: Config> Pallet<T> {
         let actual_fee = tao.saturating_sub(tao_staked);
 
         // Step 2. Swap the tao to alpha.
+        let reserves_before = Self::get_audited_reserves(netuid);
+        let spot_price = Self::get_alpha_price(netuid);
         let alpha: u64 = Self::swap_tao_for_alpha(netuid, tao_staked);
         let mut actual_alpha = 0;
         if (tao_staked > 0) && (alpha > 0) {
@@ -842,24 +2476,50 @@ impl<T: Config> Pallet<T> {
                 hotkey, coldkey, netuid, alpha,
             );
 
+            if actual_alpha == 0 {
+                // Step 3.1: The stake was lost to precision, undo the swap so the swapped alpha
+                // does not leak out of the pool and fail the stake.
+                log::error!(
+                    "Failed to credit {:?} alpha to hotkey {:?} on netuid {:?}, reverting the swap",
+                    alpha,
+                    hotkey,
+                    netuid
+                );
+                Self::revert_swap_tao_for_alpha(netuid, tao_staked, alpha);
+                return Err(Error::<T>::StakeNotCredited);
+            }
+
             // Step 4: Update the list of hotkeys staking for this coldkey
             let mut staking_hotkeys = StakingHotkeys::<T>::get(coldkey);
             if !staking_hotkeys.contains(hotkey) {
                 staking_hotkeys.push(hotkey.clone());
                 StakingHotkeys::<T>::insert(coldkey, staking_hotkeys.clone());
             }
+
+            // Step 4.1: Record the swap for audits.
+            Self::record_swap(netuid, true, reserves_before, tao_staked, alpha, actual_fee);
+            Self::record_stake_flow(netuid, tao_staked, true);
         }
 
         // Step 5. Increase Tao reserves by the fee amount.
-        SubnetTAO::<T>::mutate(netuid, |total| {
-            *total = total.saturating_add(actual_fee);
-        });
-        TotalStake::<T>::mutate(|total| {
-            *total = total.saturating_add(actual_fee);
-        });
+        Self::record_position_fee(coldkey, hotkey, netuid, actual_fee);
+        Self::credit_fee(hotkey, netuid, actual_fee);
         LastColdkeyHotkeyStakeBlock::<T>::insert(coldkey, hotkey, Self::get_current_block_as_u64());
 
-        // Step 6. Deposit and log the staking event.
+        // Step 6. Cache the result of this stake for the coldkey, hotkey pair.
+        LastStakeResult::<T>::insert(
+            coldkey,
+            hotkey,
+            (
+                netuid,
+                tao_staked,
+                actual_alpha,
+                actual_fee,
+                Self::realized_slippage(spot_price, tao_staked, actual_alpha),
+            ),
+        );
+
+        // Step 7. Deposit and log the staking event.
         Self::deposit_event(Event::StakeAdded(
             coldkey.clone(),
             hotkey.clone(),
@@ -878,8 +2538,42 @@ impl<T: Config> Pallet<T> {
             actual_fee
         );
 
-        // Step 7: Return the amount of alpha staked
-        actual_alpha
+        // Step 8: Return the amount of alpha staked
+        Ok(actual_alpha)
+    }
+
+    /// Returns the result of the most recent stake of a coldkey into a hotkey.
+    ///
+    /// # Returns
+    /// * `Option<(u16, u64, u64, u64, U96F32)>` - The (netuid, tao staked, alpha received, fee,
+    ///   realized slippage) of the last stake, or None if the coldkey never staked into the hotkey.
+    pub fn get_last_stake_result(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+    ) -> Option<(u16, u64, u64, u64, U96F32)> {
+        LastStakeResult::<T>::get(coldkey, hotkey)
+    }
+
+    /// Returns the realized slippage of the most recent stake of a coldkey into a hotkey: the
+    /// effective execution price (TAO paid per alpha received, net of the fee) minus the spot
+    /// price before the swap.
+    ///
+    /// Returns zero if the coldkey never staked into the hotkey.
+    pub fn get_last_realized_slippage(coldkey: &T::AccountId, hotkey: &T::AccountId) -> U96F32 {
+        LastStakeResult::<T>::get(coldkey, hotkey)
+            .map(|(_, _, _, _, slippage)| slippage)
+            .unwrap_or(U96F32::saturating_from_num(0))
+    }
+
+    /// Returns the difference between the execution price of swapping `tao` for `alpha` and
+    /// `spot_price`, or zero if no alpha was received.
+    fn realized_slippage(spot_price: U96F32, tao: u64, alpha: u64) -> U96F32 {
+        if alpha == 0 {
+            return U96F32::saturating_from_num(0);
+        }
+        U96F32::saturating_from_num(tao)
+            .safe_div(U96F32::saturating_from_num(alpha))
+            .saturating_sub(spot_price)
     }
 
     pub fn get_alpha_share_pool(
@@ -927,6 +2621,24 @@ impl<T: Config> Pallet<T> {
             Error::<T>::HotKeyAccountNotExists
         );
 
+        // Ensure the coldkey is allowed to delegate to this hotkey.
+        ensure!(
+            Self::is_delegation_allowed(hotkey, coldkey, netuid),
+            Error::<T>::DelegationNotAllowed
+        );
+
+        // Ensure the cooldown since the last stake by this coldkey to this hotkey has elapsed.
+        ensure!(
+            Self::stake_cooldown_elapsed(coldkey, hotkey, netuid),
+            Error::<T>::StakeCooldownNotElapsed
+        );
+
+        // Ensure the subnet has enough stakers if this is a large stake.
+        ensure!(
+            Self::large_stake_allowed(netuid, stake_to_be_added),
+            Error::<T>::NotEnoughStakersForLargeStake
+        );
+
         let expected_alpha = Self::sim_swap_tao_for_alpha(netuid, stake_to_be_added);
 
         // Ensure that we have adequate liquidity
@@ -940,9 +2652,37 @@ impl<T: Config> Pallet<T> {
         );
         ensure!(try_stake_result, Error::<T>::InsufficientLiquidity);
 
+        // Ensure the stake mints enough shares in the hotkey pool
+        ensure!(
+            Self::meets_min_share_increment(hotkey, netuid, expected_alpha.unwrap_or(0)),
+            Error::<T>::ShareIncrementTooLow
+        );
+
+        // Ensure the stake does not push the price above the subnet price ceiling.
+        ensure!(
+            !Self::exceeds_price_ceiling(netuid, stake_to_be_added, expected_alpha.unwrap_or(0)),
+            Error::<T>::PriceCeilingExceeded
+        );
+
         Ok(())
     }
 
+    /// Returns true if the subnet's stake cooldown has elapsed since the coldkey last staked
+    /// to the hotkey. Always true when the subnet has no cooldown configured.
+    pub fn stake_cooldown_elapsed(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        netuid: u16,
+    ) -> bool {
+        let cooldown = u64::from(StakeCooldownBlocks::<T>::get(netuid));
+        if cooldown == 0 {
+            return true;
+        }
+        LastColdkeyHotkeyStakeBlock::<T>::get(coldkey, hotkey).is_none_or(|last_stake_block| {
+            Self::get_current_block_as_u64().saturating_sub(last_stake_block) >= cooldown
+        })
+    }
+
     /// Validate remove_stake user input
     ///
     pub fn validate_remove_stake(
@@ -992,7 +2732,7 @@ impl<T: Config> Pallet<T> {
     ///
     pub fn validate_stake_transition(
         origin_coldkey: &T::AccountId,
-        _destination_coldkey: &T::AccountId,
+        destination_coldkey: &T::AccountId,
         origin_hotkey: &T::AccountId,
         destination_hotkey: &T::AccountId,
         origin_netuid: u16,
@@ -1010,7 +2750,7 @@ impl<T: Config> Pallet<T> {
         if origin_netuid != destination_netuid {
             ensure!(
                 Self::if_subnet_exist(destination_netuid),
-                Error::<T>::SubnetNotExists
+                Error::<T>::DestinationSubnetNotExists
             );
         }
 
@@ -1026,6 +2766,16 @@ impl<T: Config> Pallet<T> {
             Error::<T>::HotKeyAccountNotExists
         );
 
+        // Ensure the destination coldkey is allowed to delegate to the destination hotkey.
+        ensure!(
+            Self::is_delegation_allowed(
+                destination_hotkey,
+                destination_coldkey,
+                destination_netuid
+            ),
+            Error::<T>::DelegationNotAllowed
+        );
+
         // Ensure there is enough stake in the origin subnet.
         let origin_alpha = Self::get_stake_for_hotkey_and_coldkey_on_subnet(
             origin_hotkey,
@@ -1038,15 +2788,13 @@ impl<T: Config> Pallet<T> {
         );
 
         // Ensure that the stake amount to be removed is above the minimum in tao equivalent.
-        let tao_equivalent_result = Self::sim_swap_alpha_for_tao(origin_netuid, alpha_amount);
-        if let Some(tao_equivalent) = tao_equivalent_result {
-            ensure!(
-                tao_equivalent > DefaultMinStake::<T>::get(),
-                Error::<T>::AmountTooLow
-            );
-        } else {
+        let Some(tao_equivalent) = Self::sim_swap_alpha_for_tao(origin_netuid, alpha_amount) else {
             return Err(Error::<T>::InsufficientLiquidity);
-        }
+        };
+        ensure!(
+            tao_equivalent > DefaultMinStake::<T>::get(),
+            Error::<T>::AmountTooLow
+        );
 
         // Ensure that if partial execution is not allowed, the amount will not cause
         // slippage over desired
@@ -1057,7 +2805,7 @@ impl<T: Config> Pallet<T> {
         }
 
         let expected_alpha =
-            Self::sim_swap_tao_for_alpha(destination_netuid, tao_equivalent_result.unwrap_or(0))
+            Self::sim_swap_alpha_for_alpha(origin_netuid, destination_netuid, alpha_amount)
                 .unwrap_or(0);
 
         // Ensure that the amount being staked to the new hotkey is precise enough
@@ -1068,6 +2816,26 @@ impl<T: Config> Pallet<T> {
         );
         ensure!(try_stake_result, Error::<T>::InsufficientLiquidity);
 
+        // Ensure the stake mints enough shares in the destination hotkey pool
+        ensure!(
+            Self::meets_min_share_increment(destination_hotkey, destination_netuid, expected_alpha),
+            Error::<T>::ShareIncrementTooLow
+        );
+
+        if origin_netuid != destination_netuid {
+            // Ensure the stake does not push the destination price above its price ceiling.
+            ensure!(
+                !Self::exceeds_price_ceiling(destination_netuid, tao_equivalent, expected_alpha),
+                Error::<T>::PriceCeilingExceeded
+            );
+
+            // Ensure the destination subnet accepts a stake of this size.
+            ensure!(
+                Self::large_stake_allowed(destination_netuid, tao_equivalent),
+                Error::<T>::NotEnoughStakersForLargeStake
+            );
+        }
+
         if check_transfer_toggle {
             // Ensure transfer is toggled.
             ensure!(
@@ -1083,6 +2851,61 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Returns the per epoch rate of the TAO value below which the dynamic staking fee is not
+    /// allowed to fall.
+    pub fn get_dynamic_fee_floor_rate() -> U96F32 {
+        U96F32::saturating_from_num(0.00005)
+    }
+
+    /// Previews the APR floor component of the dynamic staking fee for removing `alpha` from a
+    /// subnet.
+    ///
+    /// Unlike `calculate_staking_fee`, this does not depend on the last epoch dividends, so the
+    /// preview is stable across epochs. It is a minimum: the actual fee is at least this value,
+    /// and can be higher from dividends, the `DefaultStakingFee` floor and the unstake multiplier.
+    pub fn preview_fee_stable(
+        _origin_hotkey: &T::AccountId,
+        origin_netuid: u16,
+        alpha: u64,
+    ) -> u64 {
+        let tao_estimate = U96F32::saturating_from_num(
+            Self::sim_swap_alpha_for_tao(origin_netuid, alpha).unwrap_or(0),
+        );
+        tao_estimate
+            .saturating_mul(Self::get_dynamic_fee_floor_rate())
+            .saturating_to_num::<u64>()
+    }
+
+    /// Returns the all-in price of moving `alpha` from `origin_netuid` to `dest_netuid`: the
+    /// destination alpha received per unit of origin alpha after slippage on both pools and the
+    /// staking fee.
+    ///
+    /// The move is simulated as `transition_stake_internal` executes it, each leg paying half of
+    /// the fee. Compare with `get_cross_subnet_price(origin_netuid, dest_netuid)`, the price
+    /// without slippage or fees. Returns zero if the move would not stake anything.
+    pub fn get_effective_transition_price(
+        origin_hotkey: &T::AccountId,
+        origin_netuid: u16,
+        dest_netuid: u16,
+        alpha: u64,
+    ) -> U96F32 {
+        let coldkey = Self::get_owning_coldkey_for_hotkey(origin_hotkey);
+        let fee = Self::calculate_staking_fee(
+            Some((origin_hotkey, origin_netuid)),
+            &coldkey,
+            Some((origin_hotkey, dest_netuid)),
+            &coldkey,
+            U96F32::saturating_from_num(alpha),
+        )
+        .safe_div(2);
+        let dest_alpha = Self::sim_swap_alpha_for_tao(origin_netuid, alpha)
+            .map(|tao| tao.saturating_sub(fee))
+            .filter(|tao| *tao >= DefaultMinStake::<T>::get().saturating_add(fee))
+            .and_then(|tao| Self::sim_swap_tao_for_alpha(dest_netuid, tao.saturating_sub(fee)))
+            .unwrap_or(0);
+        U96F32::saturating_from_num(dest_alpha).safe_div(U96F32::saturating_from_num(alpha))
+    }
+
     pub(crate) fn calculate_staking_fee(
         origin: Option<(&T::AccountId, u16)>,
         _origin_coldkey: &T::AccountId,
@@ -1090,6 +2913,15 @@ impl<T: Config> Pallet<T> {
         _destination_coldkey: &T::AccountId,
         alpha_estimate: U96F32,
     ) -> u64 {
+        // Staking is fee free while the subnet charging the fee is in its fee holiday: the origin
+        // for removals and moves, the destination for additions
+        if origin
+            .or(destination)
+            .is_some_and(|(_, netuid)| Self::is_in_fee_holiday(netuid))
+        {
+            return 0;
+        }
+
         match origin {
             // If origin is defined, we are removing/moving stake
             Some((origin_hotkey, origin_netuid)) => {
@@ -1101,10 +2933,12 @@ impl<T: Config> Pallet<T> {
                     }
                 }
 
-                if origin_netuid == Self::get_root_netuid()
+                let fee = if origin_netuid == Self::get_root_netuid()
                     || SubnetMechanism::<T>::get(origin_netuid) == 0
+                    || !DynamicFeeEnabled::<T>::get(origin_netuid)
                 {
-                    // If the origin netuid is root, or the subnet mechanism is 0, use the default fee
+                    // If the origin netuid is root, the subnet mechanism is 0, or the subnet opted
+                    // out of the dynamic fee, use the default fee
                     DefaultStakingFee::<T>::get()
                 } else {
                     // Otherwise, calculate the fee based on the alpha estimate
@@ -1131,7 +2965,7 @@ impl<T: Config> Pallet<T> {
 
                     // 0.005% per epoch matches to 44% annual in compound interest. Do not allow the fee
                     // to be lower than that. (1.00005^(365*20) ~= 1.44)
-                    let apr_20_percent = U96F32::saturating_from_num(0.00005);
+                    let apr_20_percent = Self::get_dynamic_fee_floor_rate();
                     fee = fee.max(
                         tao_estimate
                             .saturating_mul(apr_20_percent)
@@ -1140,12 +2974,46 @@ impl<T: Config> Pallet<T> {
 
                     // We should at least get DefaultStakingFee anyway
                     fee.max(DefaultStakingFee::<T>::get())
+                };
+
+                if destination.is_none() {
+                    // This is a pure removal, apply the subnet's unstake fee multiplier while
+                    // keeping the DefaultStakingFee floor
+                    U96F32::saturating_from_num(fee)
+                        .saturating_mul(UnstakeFeeMultiplier::<T>::get(origin_netuid))
+                        .saturating_to_num::<u64>()
+                        .max(DefaultStakingFee::<T>::get())
+                } else {
+                    fee
                 }
             }
             // If origin is not defined, we are adding stake; use default fee
             None => DefaultStakingFee::<T>::get(),
         }
     }
+
+    /// Returns the staking fee for unstaking or moving `alpha` from a hotkey on a subnet as a
+    /// fraction of the TAO value of the transaction (0.01 is 1%).
+    ///
+    /// The value is the TAO received for swapping the alpha out of the origin subnet. Returns zero
+    /// if the alpha has no TAO value.
+    pub fn get_fee_percentage(
+        origin_hotkey: &T::AccountId,
+        origin_netuid: u16,
+        destination: Option<(&T::AccountId, u16)>,
+        alpha: u64,
+    ) -> U96F32 {
+        // The fee does not depend on the coldkeys, so the hotkey stands in for both of them.
+        let fee = Self::calculate_staking_fee(
+            Some((origin_hotkey, origin_netuid)),
+            origin_hotkey,
+            destination,
+            origin_hotkey,
+            U96F32::saturating_from_num(alpha),
+        );
+        let value = Self::sim_swap_alpha_for_tao(origin_netuid, alpha).unwrap_or(0);
+        U96F32::saturating_from_num(fee).safe_div(U96F32::saturating_from_num(value))
+    }
 }
 
 ///////////////////////////////////////////
@@ -165,6 +165,14 @@ impl<T: Config> Pallet<T> {
                 let old_alpha: U64F64 = Alpha::<T>::get((&hotkey, old_coldkey, netuid));
                 // Get the stake on the new (hot,coldkey) account.
                 let new_alpha: U64F64 = Alpha::<T>::get((&hotkey, new_coldkey, netuid));
+                // Move the position to the new coldkey in the staker count.
+                if old_alpha != 0 {
+                    Self::remove_staker_position(old_coldkey, netuid);
+                    if new_alpha == 0 {
+                        Self::add_staker_position(new_coldkey, netuid);
+                    }
+                    weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 4));
+                }
                 // Add the stake to new account.
                 Alpha::<T>::insert(
                     (&hotkey, new_coldkey, netuid),
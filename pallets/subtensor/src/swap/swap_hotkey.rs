@@ -328,6 +328,12 @@ impl<T: Config> Pallet<T> {
             );
             weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
 
+            // Merging into an existing position closes one position of the coldkey.
+            if new_alpha != 0 && alpha != 0 {
+                Self::remove_staker_position(&coldkey, netuid);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+            }
+
             // Swap StakingHotkeys.
             // StakingHotkeys( coldkey ) --> Vec<hotkey> -- the hotkeys that the coldkey stakes.
             let mut staking_hotkeys = StakingHotkeys::<T>::get(&coldkey);
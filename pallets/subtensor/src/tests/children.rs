@@ -7,7 +7,7 @@ use frame_support::{assert_err, assert_noop, assert_ok};
 use substrate_fixed::types::{I64F64, I96F32, U96F32};
 
 use crate::{utils::rate_limiting::TransactionType, *};
-use sp_core::U256;
+use sp_core::{Get, U256};
 
 fn close(value: u64, target: u64, eps: u64, msg: &str) {
     assert!(
@@ -92,7 +92,7 @@ fn test_do_set_child_singular_invalid_child() {
                     (proportion, hotkey) // Invalid child
                 ]
             ),
-            Error::<Test>::InvalidChild
+            Error::<Test>::SelfReferentialDelegation
         );
     });
 }
@@ -351,7 +351,7 @@ fn test_add_singular_child() {
                 netuid,
                 vec![(u64::MAX, child)]
             ),
-            Err(Error::<Test>::InvalidChild.into())
+            Err(Error::<Test>::SelfReferentialDelegation.into())
         );
         let child = U256::from(3);
         step_rate_limit(&TransactionType::SetChildren, netuid);
@@ -621,7 +621,7 @@ fn test_do_schedule_children_multiple_invalid_child() {
                 netuid,
                 vec![(proportion, hotkey)]
             ),
-            Error::<Test>::InvalidChild
+            Error::<Test>::SelfReferentialDelegation
         );
     });
 }
@@ -3988,3 +3988,125 @@ fn test_pending_cooldown_one_day() {
         assert_eq!(pending_children.1, curr_block + expected_cooldown);
     });
 }
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::children::test_do_schedule_children_too_many_parents --exact --show-output --nocapture
+#[test]
+fn test_do_schedule_children_too_many_parents() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let child = U256::from(3);
+        let netuid: u16 = 1;
+        let proportion: u64 = 1000;
+
+        // Add network and register hotkey
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        StakeThreshold::<Test>::put(0);
+
+        // Fill the child's parents up to the bound
+        let max_parents = <Test as Config>::MaxParents::get() as u64;
+        let parents: Vec<(u64, U256)> = (0..max_parents)
+            .map(|i| (proportion, U256::from(100 + i)))
+            .collect();
+        ParentKeys::<Test>::insert(child, netuid, parents);
+
+        // One more parent is rejected
+        assert_noop!(
+            SubtensorModule::do_schedule_children(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                vec![(proportion, child)]
+            ),
+            Error::<Test>::TooManyParents
+        );
+
+        // Too many children are rejected as well
+        let max_children = <Test as Config>::MaxChildren::get() as u64;
+        let children: Vec<(u64, U256)> = (0..=max_children)
+            .map(|i| (proportion, U256::from(200 + i)))
+            .collect();
+        assert_noop!(
+            SubtensorModule::do_schedule_children(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                children
+            ),
+            Error::<Test>::TooManyChildren
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::children::test_preview_children_impact --exact --show-output --nocapture
+#[test]
+fn test_preview_children_impact() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let parent = U256::from(1);
+        let child = U256::from(2);
+        let coldkey = U256::from(3);
+        add_network(netuid, 1, 0);
+        register_ok_neuron(netuid, parent, coldkey, 0);
+        register_ok_neuron(netuid, child, coldkey, 0);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &parent, &coldkey, netuid, 1_000_000,
+        );
+        let children = vec![(u64::MAX / 4, child)];
+
+        // Without children the hotkey keeps all of its stake
+        let before = SubtensorModule::get_inherited_for_hotkey_on_subnet(&parent, netuid);
+        assert_eq!(before, 1_000_000);
+
+        // The preview leaves the children unset
+        let preview = SubtensorModule::preview_children_impact(&parent, netuid, children.clone());
+        assert!(preview < before);
+        assert!(SubtensorModule::get_children(&parent, netuid).is_empty());
+
+        // And matches the inherited alpha once the children are actually set
+        mock_set_children_no_epochs(netuid, &parent, &children);
+        assert_eq!(
+            SubtensorModule::get_inherited_for_hotkey_on_subnet(&parent, netuid),
+            preview
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::children::test_self_referential_delegation --exact --show-output --nocapture
+#[test]
+fn test_self_referential_delegation() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(netuid, 1, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, 1_000_000,
+        );
+
+        // A hotkey cannot become its own parent
+        assert_noop!(
+            SubtensorModule::do_schedule_children(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                vec![(u64::MAX / 2, hotkey)]
+            ),
+            Error::<Test>::SelfReferentialDelegation
+        );
+
+        // Pre-existing self entries are ignored by the inheritance math
+        ChildKeys::<Test>::insert(hotkey, netuid, vec![(u64::MAX / 2, hotkey)]);
+        ParentKeys::<Test>::insert(hotkey, netuid, vec![(u64::MAX / 2, hotkey)]);
+        assert_eq!(
+            SubtensorModule::get_inherited_for_hotkey_on_subnet(&hotkey, netuid),
+            1_000_000
+        );
+        assert_eq!(
+            SubtensorModule::preview_children_impact(&hotkey, netuid, vec![(u64::MAX / 2, hotkey)]),
+            1_000_000
+        );
+    });
+}
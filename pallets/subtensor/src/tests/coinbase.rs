@@ -209,6 +209,8 @@ fn test_coinbase_moving_prices() {
         // Set price to zero.
         SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(0));
         SubnetMovingAlpha::<Test>::set(I96F32::from_num(1.0));
+        // The moving price advances at most once per block.
+        System::set_block_number(7_200_001);
         // Run moving 1 times.
         SubtensorModule::update_moving_price(netuid);
         // Assert price is ~ 100% of the real price.
@@ -220,8 +222,9 @@ fn test_coinbase_moving_prices() {
         // EMA price 28 days after registration
         System::set_block_number(7_200 * 28);
 
-        // Run moving 14 times.
-        for _ in 0..14 {
+        // Run moving 14 times, one per block.
+        for i in 0..14 {
+            System::set_block_number(7_200 * 28 + i);
             SubtensorModule::update_moving_price(netuid);
         }
 
@@ -234,6 +237,141 @@ fn test_coinbase_moving_prices() {
     });
 }
 
+// Test the moving price only advances once per block.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_update_moving_price_once_per_block --exact --show-output --nocapture
+#[test]
+fn test_update_moving_price_once_per_block() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, 1, 0);
+        // Set current price to 1.0
+        SubnetTAO::<Test>::insert(netuid, 1_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000);
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        SubnetMovingAlpha::<Test>::set(I96F32::from_num(0.1));
+        SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(0));
+        FirstEmissionBlockNumber::<Test>::insert(netuid, 1);
+        System::set_block_number(7_200_000);
+
+        SubtensorModule::update_moving_price(netuid);
+        let first = SubnetMovingPrice::<Test>::get(netuid);
+        assert!(first > I96F32::from_num(0));
+        assert_eq!(
+            LastMovingPriceUpdateBlock::<Test>::get(netuid),
+            Some(7_200_000)
+        );
+
+        // A second update in the same block is a no-op.
+        SubtensorModule::update_moving_price(netuid);
+        assert_eq!(SubnetMovingPrice::<Test>::get(netuid), first);
+
+        // The next block advances the price again.
+        System::set_block_number(7_200_001);
+        SubtensorModule::update_moving_price(netuid);
+        assert!(SubnetMovingPrice::<Test>::get(netuid) > first);
+    });
+}
+
+// Test moving price updates emit their intermediate values when debug events are enabled.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_update_moving_price_debug_event --exact --show-output --nocapture
+#[test]
+fn test_update_moving_price_debug_event() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, 1, 0);
+        // Set current price to 0.5
+        SubnetTAO::<Test>::insert(netuid, 500_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000);
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        SubnetMovingAlpha::<Test>::set(I96F32::from_num(0.5));
+        SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(0));
+        EMAPriceHalvingBlocks::<Test>::insert(netuid, 100);
+        FirstEmissionBlockNumber::<Test>::insert(netuid, 101);
+        System::set_block_number(200);
+        let is_debug_event = |e: &RuntimeEvent| {
+            matches!(
+                e,
+                RuntimeEvent::SubtensorModule(Event::MovingPriceDebug(..))
+            )
+        };
+
+        // Disabled by default
+        SubtensorModule::update_moving_price(netuid);
+        assert!(!System::events().iter().any(|e| is_debug_event(&e.event)));
+
+        SubtensorModule::set_ema_debug_events(true);
+        System::set_block_number(300);
+        SubtensorModule::update_moving_price(netuid);
+
+        // 200 blocks since start call with a halving time of 100: alpha = 0.5 * 200 / 300
+        let event = System::events()
+            .into_iter()
+            .map(|e| e.event)
+            .find(is_debug_event);
+        let Some(RuntimeEvent::SubtensorModule(Event::MovingPriceDebug(
+            event_netuid,
+            blocks,
+            alpha,
+            spot_price,
+            moving_price,
+        ))) = event
+        else {
+            panic!("expected a MovingPriceDebug event");
+        };
+        assert_eq!(event_netuid, netuid);
+        assert_eq!(blocks, 200);
+        assert_abs_diff_eq!(alpha.to_num::<f64>(), 1.0 / 3.0, epsilon = 0.000_001);
+        assert_eq!(spot_price, U96F32::from_num(0.5));
+        assert_eq!(moving_price, SubnetMovingPrice::<Test>::get(netuid));
+    });
+}
+
+// Test the moving price preview matches the update when given the real spot price.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_preview_next_moving_price --exact --show-output --nocapture
+#[test]
+fn test_preview_next_moving_price() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, 1, 0);
+        // Set current price to 0.5
+        SubnetTAO::<Test>::insert(netuid, 500_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000);
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        SubnetMovingAlpha::<Test>::set(I96F32::from_num(0.5));
+        SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(0.2));
+        EMAPriceHalvingBlocks::<Test>::insert(netuid, 100);
+        FirstEmissionBlockNumber::<Test>::insert(netuid, 101);
+        System::set_block_number(300);
+
+        // A higher spot pulls the moving price up more, but is clamped to 1.0
+        let preview_low = SubtensorModule::preview_next_moving_price(netuid, U96F32::from_num(0.5));
+        let preview_high =
+            SubtensorModule::preview_next_moving_price(netuid, U96F32::from_num(0.9));
+        let preview_clamped =
+            SubtensorModule::preview_next_moving_price(netuid, U96F32::from_num(5.0));
+        assert!(preview_low < preview_high);
+        assert_eq!(
+            preview_clamped,
+            SubtensorModule::preview_next_moving_price(netuid, U96F32::from_num(1.0))
+        );
+
+        // The preview does not persist
+        assert_eq!(
+            SubnetMovingPrice::<Test>::get(netuid),
+            I96F32::from_num(0.2)
+        );
+
+        // With the real spot price the preview equals the update
+        let preview = SubtensorModule::preview_next_moving_price(
+            netuid,
+            SubtensorModule::get_alpha_price(netuid),
+        );
+        SubtensorModule::update_moving_price(netuid);
+        assert_eq!(preview, SubnetMovingPrice::<Test>::get(netuid));
+        assert_eq!(preview, preview_low);
+    });
+}
+
 // Test moving price updates slow down at the beginning.
 // SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_update_moving_price_initial --exact --show-output --nocapture
 #[test]
@@ -284,6 +422,41 @@ fn test_update_moving_price_after_time() {
     });
 }
 
+// Test all dynamic subnets' moving prices advance in one batched update.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_update_all_moving_prices --exact --show-output --nocapture
+#[test]
+fn test_update_all_moving_prices() {
+    new_test_ext(1).execute_with(|| {
+        let dynamic_netuids: [u16; 2] = [1, 2];
+        let stable_netuid: u16 = 3;
+        for netuid in dynamic_netuids.iter().chain([stable_netuid].iter()) {
+            add_network(*netuid, 1, 0);
+            SubnetTAO::<Test>::insert(*netuid, 1_000_000);
+            SubnetAlphaIn::<Test>::insert(*netuid, 1_000_000);
+            SubnetMovingPrice::<Test>::insert(*netuid, I96F32::from_num(0));
+            FirstEmissionBlockNumber::<Test>::insert(*netuid, 500);
+        }
+        for netuid in dynamic_netuids.iter() {
+            SubnetMechanism::<Test>::insert(*netuid, 1);
+        }
+        SubnetMechanism::<Test>::insert(stable_netuid, 0);
+        SubnetMovingAlpha::<Test>::set(I96F32::from_num(0.5));
+        System::set_block_number(144_000_500);
+
+        let weight = SubtensorModule::update_all_moving_prices();
+        assert!(weight.ref_time() > 0);
+
+        for netuid in dynamic_netuids.iter() {
+            let new_price = SubnetMovingPrice::<Test>::get(*netuid);
+            assert!((new_price.to_num::<f64>() - 0.5).abs() < 0.001);
+        }
+        assert_eq!(
+            SubnetMovingPrice::<Test>::get(stable_netuid),
+            I96F32::from_num(0)
+        );
+    });
+}
+
 // Test basic alpha issuance in coinbase mechanism.
 // This test verifies that:
 // - Alpha issuance is initialized to 0 for new subnets
@@ -2131,3 +2304,39 @@ fn test_run_coinbase_not_started_start_after() {
         log::info!("new_stake: {}", new_stake);
     });
 }
+
+// Test that the self contribution uses the subnet's tao weight override.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_get_self_contribution_uses_subnet_tao_weight --exact --show-output --nocapture
+#[test]
+fn test_get_self_contribution_uses_subnet_tao_weight() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(netuid, 1, 0);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            SubtensorModule::get_root_netuid(),
+            1_000_000,
+        );
+
+        // The global weight counts the root stake fully
+        SubtensorModule::set_tao_weight(u64::MAX);
+        assert_eq!(
+            SubtensorModule::get_self_contribution(&hotkey, netuid),
+            1_000_000
+        );
+
+        // The subnet override ignores the root stake
+        SubtensorModule::set_subnet_tao_weight(netuid, Some(0));
+        assert_eq!(SubtensorModule::get_self_contribution(&hotkey, netuid), 0);
+
+        // Clearing the override restores the global weight
+        SubtensorModule::set_subnet_tao_weight(netuid, None);
+        assert_eq!(
+            SubtensorModule::get_self_contribution(&hotkey, netuid),
+            1_000_000
+        );
+    });
+}
@@ -6,7 +6,7 @@ use alloc::collections::BTreeMap;
 use approx::assert_abs_diff_eq;
 use codec::{Decode, Encode};
 use frame_support::{
-    StorageHasher, Twox64Concat, assert_ok,
+    Blake2_128Concat, StorageHasher, Twox64Concat, assert_ok,
     storage::unhashed::{get, get_raw, put, put_raw},
     traits::{StorageInstance, StoredMap},
     weights::Weight,
@@ -16,8 +16,8 @@ use frame_system::Config;
 use sp_core::{H256, U256, crypto::Ss58Codec};
 use sp_io::hashing::twox_128;
 use sp_runtime::traits::Zero;
-use substrate_fixed::types::I96F32;
 use substrate_fixed::types::extra::U2;
+use substrate_fixed::types::{I96F32, U64F64, U96F32};
 
 #[allow(clippy::arithmetic_side_effects)]
 fn close(value: u64, target: u64, eps: u64) {
@@ -481,6 +481,86 @@ fn test_migrate_remove_zero_total_hotkey_alpha() {
     });
 }
 
+#[test]
+fn test_migrate_seed_subnet_staker_count() {
+    new_test_ext(1).execute_with(|| {
+        const MIGRATION_NAME: &str = "migrate_seed_subnet_staker_count";
+        let coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let coldkey2 = U256::from(4);
+
+        // Two positions of the same coldkey and one of another coldkey on subnet 1, one
+        // position on subnet 2 and an emptied position that no longer counts
+        Alpha::<Test>::insert((hotkey1, coldkey, 1), U64F64::from_num(10));
+        Alpha::<Test>::insert((hotkey2, coldkey, 1), U64F64::from_num(20));
+        Alpha::<Test>::insert((hotkey1, coldkey2, 1), U64F64::from_num(15));
+        Alpha::<Test>::insert((hotkey1, coldkey, 2), U64F64::from_num(30));
+        Alpha::<Test>::insert((hotkey2, coldkey, 2), U64F64::from_num(0));
+        SubnetStakerCount::<Test>::insert(3, 7);
+
+        let weight =
+            crate::migrations::migrate_seed_subnet_staker_count::migrate_seed_subnet_staker_count::<
+                Test,
+            >();
+
+        assert!(HasMigrationRun::<Test>::get(
+            MIGRATION_NAME.as_bytes().to_vec()
+        ));
+        assert_eq!(SubnetStakerCount::<Test>::get(1), 2);
+        assert_eq!(SubnetStakerCount::<Test>::get(2), 1);
+        assert_eq!(SubnetStakerCount::<Test>::get(3), 0);
+        assert_eq!(SubnetColdkeyPositions::<Test>::get(1, coldkey), 2);
+        assert_eq!(SubnetColdkeyPositions::<Test>::get(1, coldkey2), 1);
+        assert_eq!(SubnetColdkeyPositions::<Test>::get(2, coldkey), 1);
+        assert_eq!(SubnetColdkeyPositions::<Test>::get(2, coldkey2), 0);
+        assert!(!weight.is_zero());
+    });
+}
+
+#[test]
+fn test_migrate_last_stake_result_slippage() {
+    new_test_ext(1).execute_with(|| {
+        const MIGRATION_NAME: &str = "migrate_last_stake_result_slippage";
+        let coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let slippage = U96F32::from_num(0.25);
+
+        // Old stake results, only the first with a cached slippage
+        put::<(u16, u64, u64, u64)>(
+            &LastStakeResult::<Test>::hashed_key_for(coldkey, hotkey1),
+            &(1, 100, 90, 10),
+        );
+        put::<(u16, u64, u64, u64)>(
+            &LastStakeResult::<Test>::hashed_key_for(coldkey, hotkey2),
+            &(2, 200, 180, 20),
+        );
+        let mut slippage_key = Vec::new();
+        slippage_key.extend_from_slice(&twox_128("SubtensorModule".as_bytes()));
+        slippage_key.extend_from_slice(&twox_128("LastStakeSlippage".as_bytes()));
+        slippage_key.extend_from_slice(&Blake2_128Concat::hash(&coldkey.encode()));
+        slippage_key.extend_from_slice(&Blake2_128Concat::hash(&hotkey1.encode()));
+        put::<U96F32>(&slippage_key, &slippage);
+
+        let weight = crate::migrations::migrate_last_stake_result_slippage::migrate_last_stake_result_slippage::<Test>();
+
+        assert!(HasMigrationRun::<Test>::get(
+            MIGRATION_NAME.as_bytes().to_vec()
+        ));
+        assert_eq!(
+            LastStakeResult::<Test>::get(coldkey, hotkey1),
+            Some((1, 100, 90, 10, slippage))
+        );
+        assert_eq!(
+            LastStakeResult::<Test>::get(coldkey, hotkey2),
+            Some((2, 200, 180, 20, U96F32::from_num(0)))
+        );
+        assert_eq!(get::<U96F32>(&slippage_key), None);
+        assert!(!weight.is_zero());
+    });
+}
+
 #[test]
 fn test_migrate_revealed_commitments() {
     new_test_ext(1).execute_with(|| {
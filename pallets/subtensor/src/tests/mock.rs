@@ -18,6 +18,7 @@ use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::cmp::Ordering;
+use substrate_fixed::types::U96F32;
 
 use crate::*;
 
@@ -186,6 +187,8 @@ parameter_types! {
     pub const InitialTaoWeight: u64 = 0; // 100% global weight.
     pub const InitialEmaPriceHalvingPeriod: u64 = 201_600_u64; // 4 weeks
     pub const DurationOfStartCall: u64 =  7 * 24 * 60 * 60 / 12; // Default as 7 days
+    pub const MaxChildren: u32 = 5;
+    pub const MaxParents: u32 = 10;
 }
 
 // Configure collective pallet for council
@@ -410,6 +413,8 @@ impl crate::Config for Test {
     type InitialTaoWeight = InitialTaoWeight;
     type InitialEmaPriceHalvingPeriod = InitialEmaPriceHalvingPeriod;
     type DurationOfStartCall = DurationOfStartCall;
+    type MaxChildren = MaxChildren;
+    type MaxParents = MaxParents;
 }
 
 pub struct OriginPrivilegeCmp;
@@ -736,6 +741,64 @@ pub fn setup_neuron_with_stake(netuid: u16, hotkey: U256, coldkey: U256, stake:
     increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, stake, netuid);
 }
 
+// Helper function to check the pool accounting of a subnet, e.g. after each operation of a
+// randomized stake/unstake sequence
+#[allow(dead_code)]
+pub fn assert_pool_invariants(netuid: u16) {
+    let tao_reserve = SubnetTAO::<Test>::get(netuid);
+    let alpha_in = SubnetAlphaIn::<Test>::get(netuid);
+    let alpha_out = SubnetAlphaOut::<Test>::get(netuid);
+
+    // The issuance is the sum of both sides and must not overflow
+    assert_eq!(
+        alpha_in.checked_add(alpha_out),
+        Some(SubtensorModule::get_alpha_issuance(netuid)),
+        "alpha issuance overflow on netuid {netuid}"
+    );
+
+    // Dynamic pools are priced by their reserves
+    if SubnetMechanism::<Test>::get(netuid) == 1 && alpha_in > 0 {
+        assert_eq!(
+            SubtensorModule::get_alpha_price(netuid),
+            U96F32::from_num(tao_reserve) / U96F32::from_num(alpha_in),
+            "inconsistent price on netuid {netuid}"
+        );
+    } else if SubnetMechanism::<Test>::get(netuid) == 0 {
+        assert_eq!(
+            SubtensorModule::get_alpha_price(netuid),
+            U96F32::from_num(1)
+        );
+    }
+
+    // Hotkey alpha is backed by the outstanding alpha
+    assert!(
+        SubtensorModule::check_alpha_conservation(netuid),
+        "hotkey alpha exceeds outstanding alpha on netuid {netuid}"
+    );
+
+    // The shares of the coldkeys of each hotkey add up to the share pool denominator
+    for (hotkey, hotkey_netuid, total_shares) in TotalHotkeyShares::<Test>::iter() {
+        if hotkey_netuid != netuid {
+            continue;
+        }
+        let coldkey_shares: f64 = Alpha::<Test>::iter_prefix((hotkey,))
+            .filter(|((_, alpha_netuid), _)| *alpha_netuid == netuid)
+            .map(|(_, shares)| shares.to_num::<f64>())
+            .sum();
+        let total_shares = total_shares.to_num::<f64>();
+        assert!(
+            (coldkey_shares - total_shares).abs() <= total_shares * 1e-9 + 1.0,
+            "shares of hotkey {hotkey} sum to {coldkey_shares}, expected {total_shares}"
+        );
+        if TotalHotkeyAlpha::<Test>::get(hotkey, netuid) > 0 {
+            assert!(
+                total_shares > 0.0,
+                "hotkey {hotkey} has alpha without shares"
+            );
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub fn wait_set_pending_children_cooldown(netuid: u16) {
     let cooldown = DefaultPendingCooldown::<Test>::get();
@@ -805,7 +868,7 @@ pub fn increase_stake_on_coldkey_hotkey_account(
     netuid: u16,
 ) {
     let fee = 0;
-    SubtensorModule::stake_into_subnet(hotkey, coldkey, netuid, tao_staked, fee);
+    SubtensorModule::stake_into_subnet(hotkey, coldkey, netuid, tao_staked, fee).unwrap();
 }
 
 /// Increases the stake on the hotkey account under its owning coldkey.
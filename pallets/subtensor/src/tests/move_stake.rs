@@ -1,3 +1,5 @@
+#![allow(clippy::unwrap_used)]
+
 use super::mock::*;
 use crate::*;
 use approx::assert_abs_diff_eq;
@@ -23,7 +25,8 @@ fn test_do_move_success() {
         // Set up initial stake
         SubtensorModule::create_account_if_non_existent(&coldkey, &origin_hotkey);
         SubtensorModule::create_account_if_non_existent(&coldkey, &destination_hotkey);
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee);
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -86,7 +89,8 @@ fn test_do_move_different_subnets() {
             origin_netuid,
             stake_amount,
             fee,
-        );
+        )
+        .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -152,7 +156,8 @@ fn test_do_move_nonexistent_subnet() {
             origin_netuid,
             stake_amount,
             fee,
-        );
+        )
+        .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -169,7 +174,7 @@ fn test_do_move_nonexistent_subnet() {
                 nonexistent_netuid,
                 alpha,
             ),
-            Error::<Test>::SubnetNotExists
+            Error::<Test>::DestinationSubnetNotExists
         );
 
         // Check that the stake remains unchanged
@@ -247,7 +252,8 @@ fn test_do_move_nonexistent_destination_hotkey() {
         // Set up initial stake
         SubtensorModule::create_account_if_non_existent(&coldkey, &origin_hotkey);
         let alpha =
-            SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee);
+            SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee)
+                .unwrap();
 
         // Attempt to move stake from a non-existent origin hotkey
         add_network(netuid, 1, 0);
@@ -299,7 +305,8 @@ fn test_do_move_all_stake() {
         let fee = DefaultStakingFee::<Test>::get();
 
         // Set up initial stake
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee);
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -352,7 +359,8 @@ fn test_do_move_half_stake() {
         let fee = DefaultStakingFee::<Test>::get();
 
         // Set up initial stake
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee);
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -409,7 +417,8 @@ fn test_do_move_partial_stake() {
         let fee = DefaultStakingFee::<Test>::get();
 
         // Set up initial stake
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, total_stake, fee);
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, total_stake, fee)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -467,7 +476,7 @@ fn test_do_move_multiple_times() {
         // Set up initial stake
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey1);
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey2);
-        SubtensorModule::stake_into_subnet(&hotkey1, &coldkey, netuid, initial_stake, fee);
+        SubtensorModule::stake_into_subnet(&hotkey1, &coldkey, netuid, initial_stake, fee).unwrap();
 
         // Move stake multiple times
         for _ in 0..3 {
@@ -523,7 +532,8 @@ fn test_do_move_wrong_origin() {
         let fee = 0;
 
         // Set up initial stake
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee);
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, fee)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -582,7 +592,7 @@ fn test_do_move_same_hotkey() {
 
         // Set up initial stake
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, stake_amount, fee);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, stake_amount, fee).unwrap();
         let alpha =
             SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
 
@@ -623,7 +633,8 @@ fn test_do_move_event_emission() {
         // Set up initial stake
         SubtensorModule::create_account_if_non_existent(&coldkey, &origin_hotkey);
         SubtensorModule::create_account_if_non_existent(&coldkey, &destination_hotkey);
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, 0); // use 0 fee for precision
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, stake_amount, 0)
+            .unwrap(); // use 0 fee for precision
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -679,7 +690,8 @@ fn test_do_move_storage_updates() {
             origin_netuid,
             stake_amount,
             fee,
-        );
+        )
+        .unwrap();
 
         // Move stake
         SubtensorModule::create_account_if_non_existent(&coldkey, &origin_hotkey);
@@ -747,7 +759,8 @@ fn test_do_move_max_values() {
         SubnetTAO::<Test>::insert(netuid, u64::MAX / 1000);
         SubnetAlphaIn::<Test>::insert(netuid, u64::MAX / 1000);
 
-        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, max_stake, fee);
+        SubtensorModule::stake_into_subnet(&origin_hotkey, &coldkey, netuid, max_stake, fee)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &origin_hotkey,
             &coldkey,
@@ -840,7 +853,8 @@ fn test_do_transfer_success() {
         // 3. Set up initial stake: (origin_coldkey, hotkey) on netuid.
         SubtensorModule::create_account_if_non_existent(&origin_coldkey, &hotkey);
         SubtensorModule::create_account_if_non_existent(&destination_coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, 0)
+            .unwrap();
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &hotkey,
             &origin_coldkey,
@@ -939,7 +953,8 @@ fn test_do_transfer_insufficient_stake() {
         let stake_amount = DefaultMinStake::<Test>::get() * 10;
 
         SubtensorModule::create_account_if_non_existent(&origin_coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, 0)
+            .unwrap();
 
         let alpha = stake_amount * 2;
         assert_noop!(
@@ -972,7 +987,8 @@ fn test_do_transfer_wrong_origin() {
 
         SubtensorModule::create_account_if_non_existent(&origin_coldkey, &hotkey);
         SubtensorModule::add_balance_to_coldkey_account(&origin_coldkey, stake_amount + fee);
-        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, fee);
+        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, fee)
+            .unwrap();
 
         assert_noop!(
             SubtensorModule::do_transfer_stake(
@@ -1001,7 +1017,8 @@ fn test_do_transfer_minimum_stake_check() {
 
         let stake_amount = DefaultMinStake::<Test>::get();
         SubtensorModule::create_account_if_non_existent(&origin_coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &origin_coldkey, netuid, stake_amount, 0)
+            .unwrap();
 
         assert_err!(
             SubtensorModule::do_transfer_stake(
@@ -1047,7 +1064,8 @@ fn test_do_transfer_different_subnets() {
             origin_netuid,
             stake_amount,
             0,
-        );
+        )
+        .unwrap();
 
         // 6. Transfer entire stake from origin_netuid -> destination_netuid.
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
@@ -1104,7 +1122,8 @@ fn test_do_swap_success() {
         let fee = DefaultStakingFee::<Test>::get();
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0)
+            .unwrap();
         let alpha_before = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &hotkey,
             &coldkey,
@@ -1208,7 +1227,7 @@ fn test_do_swap_insufficient_stake() {
         let attempted_swap = stake_amount * 2;
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid1, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid1, stake_amount, 0).unwrap();
 
         assert_noop!(
             SubtensorModule::do_swap_stake(
@@ -1237,7 +1256,8 @@ fn test_do_swap_wrong_origin() {
         let stake_amount = 100_000;
 
         SubtensorModule::create_account_if_non_existent(&real_coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &real_coldkey, netuid1, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &real_coldkey, netuid1, stake_amount, 0)
+            .unwrap();
 
         assert_noop!(
             SubtensorModule::do_swap_stake(
@@ -1265,7 +1285,7 @@ fn test_do_swap_minimum_stake_check() {
         let swap_amount = 1;
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, total_stake, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, total_stake, 0).unwrap();
 
         assert_err!(
             SubtensorModule::do_swap_stake(
@@ -1293,7 +1313,7 @@ fn test_do_swap_same_subnet() {
         let fee = DefaultStakingFee::<Test>::get();
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, stake_amount, 0).unwrap();
 
         let alpha_before =
             SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
@@ -1331,7 +1351,8 @@ fn test_do_swap_partial_stake() {
         let fee = DefaultStakingFee::<Test>::get();
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, total_stake, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, total_stake, 0)
+            .unwrap();
 
         let swap_amount = total_stake / 2;
         assert_ok!(SubtensorModule::do_swap_stake(
@@ -1383,7 +1404,8 @@ fn test_do_swap_storage_updates() {
         let fee = DefaultStakingFee::<Test>::get();
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0)
+            .unwrap();
 
         let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &hotkey,
@@ -1438,7 +1460,7 @@ fn test_do_swap_multiple_times() {
         let fee = DefaultStakingFee::<Test>::get();
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid1, initial_stake, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid1, initial_stake, 0).unwrap();
 
         let mut total_alpha1_fee = 0;
         for _ in 0..3 {
@@ -1503,7 +1525,8 @@ fn test_do_swap_allows_non_owned_hotkey() {
         let stake_amount = DefaultMinStake::<Test>::get() * 10;
 
         SubtensorModule::create_account_if_non_existent(&foreign_coldkey, &hotkey);
-        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0)
+            .unwrap();
         let alpha_before = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
             &hotkey,
             &coldkey,
@@ -1538,7 +1561,8 @@ fn test_swap_stake_limit_validate() {
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
         let unstake_amount =
-            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0);
+            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0)
+                .unwrap();
 
         // Setup limit price so that it doesn't allow much slippage at all
         let limit_price = ((SubtensorModule::get_alpha_price(origin_netuid)
@@ -1592,7 +1616,8 @@ fn test_stake_transfers_disabled_validate() {
 
         SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
         let unstake_amount =
-            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0);
+            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0)
+                .unwrap();
 
         // Swap stake limit call
         let call = RuntimeCall::SubtensorModule(SubtensorCall::transfer_stake {
@@ -1752,3 +1777,269 @@ fn test_move_stake_specific_stake_into_subnet_fail() {
         );
     });
 }
+
+// Description: A transfer whose computed fee exceeds the caller's cap is rejected, and succeeds
+// once the cap covers the fee.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --test move -- test_do_transfer_with_max_fee --exact --nocapture
+#[test]
+fn test_do_transfer_with_max_fee() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let origin_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let destination_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+
+        let origin_coldkey = U256::from(1);
+        let destination_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let stake_amount = 100_000_000_000;
+
+        // Deep pools so the swaps are close to 1:1
+        SubnetTAO::<Test>::insert(origin_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(origin_netuid, 1_000_000_000_000);
+        SubnetTAO::<Test>::insert(destination_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(destination_netuid, 1_000_000_000_000);
+
+        SubtensorModule::create_account_if_non_existent(&origin_coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(
+            &hotkey,
+            &origin_coldkey,
+            origin_netuid,
+            stake_amount,
+            0,
+        )
+        .unwrap();
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &origin_coldkey,
+            origin_netuid,
+        );
+
+        // The hotkey earned 10% dividends last epoch, which makes the cross-subnet fee large
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, origin_netuid, alpha);
+        AlphaDividendsPerSubnet::<Test>::insert(origin_netuid, hotkey, alpha / 10);
+        let fee = SubtensorModule::calculate_staking_fee(
+            Some((&hotkey, origin_netuid)),
+            &origin_coldkey,
+            Some((&hotkey, destination_netuid)),
+            &destination_coldkey,
+            U96F32::from_num(alpha),
+        );
+        assert!(fee > DefaultStakingFee::<Test>::get());
+
+        // Fee above the cap is rejected
+        assert_noop!(
+            SubtensorModule::transfer_stake_with_max_fee(
+                RuntimeOrigin::signed(origin_coldkey),
+                destination_coldkey,
+                hotkey,
+                origin_netuid,
+                destination_netuid,
+                alpha,
+                fee - 1,
+            ),
+            Error::<Test>::FeeExceedsMaximum
+        );
+
+        // Fee within the cap goes through
+        assert_ok!(SubtensorModule::transfer_stake_with_max_fee(
+            RuntimeOrigin::signed(origin_coldkey),
+            destination_coldkey,
+            hotkey,
+            origin_netuid,
+            destination_netuid,
+            alpha,
+            fee,
+        ));
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &origin_coldkey,
+                origin_netuid
+            ),
+            0
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --test move -- test_do_move_nonexistent_origin_subnet --exact --nocapture
+#[test]
+fn test_do_move_nonexistent_origin_subnet() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let destination_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let coldkey = U256::from(1);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let nonexistent_netuid = 99;
+        let stake_amount = DefaultMinStake::<Test>::get() * 10;
+
+        // The origin side reports the generic error
+        assert_noop!(
+            SubtensorModule::do_move_stake(
+                RuntimeOrigin::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                nonexistent_netuid,
+                destination_netuid,
+                stake_amount,
+            ),
+            Error::<Test>::SubnetNotExists
+        );
+
+        // The destination side reports its own error
+        assert_noop!(
+            SubtensorModule::do_move_stake(
+                RuntimeOrigin::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                destination_netuid,
+                nonexistent_netuid,
+                stake_amount,
+            ),
+            Error::<Test>::DestinationSubnetNotExists
+        );
+    });
+}
+
+// Description: A cross-subnet move into a shallow destination pool receives less alpha than the
+// caller's minimum and is rejected, while a reachable minimum goes through.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --test move -- test_do_move_with_min_dest_alpha --exact --nocapture
+#[test]
+fn test_do_move_with_min_dest_alpha() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let origin_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let destination_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let stake_amount = 100_000_000_000;
+
+        // Deep origin pool, shallow destination pool at the same price
+        SubnetTAO::<Test>::insert(origin_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(origin_netuid, 1_000_000_000_000);
+        SubnetTAO::<Test>::insert(destination_netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(destination_netuid, 100_000_000_000);
+
+        SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, stake_amount, 0)
+            .unwrap();
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            origin_netuid,
+        );
+
+        // Slippage on the destination leaves roughly half of the alpha
+        assert_noop!(
+            SubtensorModule::move_stake_with_min_dest_alpha(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                hotkey,
+                origin_netuid,
+                destination_netuid,
+                alpha,
+                alpha * 9 / 10,
+            ),
+            Error::<Test>::DestinationAlphaTooLow
+        );
+
+        assert_ok!(SubtensorModule::move_stake_with_min_dest_alpha(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            hotkey,
+            origin_netuid,
+            destination_netuid,
+            alpha,
+            alpha / 3,
+        ));
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                destination_netuid
+            ) >= alpha / 3
+        );
+    });
+}
+
+// Description: Moving stake between two hotkeys on the same subnet only moves shares. The fee
+// is sold into the pool and its TAO is credited back to the reserve like any staking fee.
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --test move -- test_do_swap_hotkey_stake --exact --nocapture
+#[test]
+fn test_do_swap_hotkey_stake() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+
+        let coldkey = U256::from(1);
+        let from_hotkey = U256::from(2);
+        let to_hotkey = U256::from(3);
+
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubtensorModule::create_account_if_non_existent(&coldkey, &from_hotkey);
+        SubtensorModule::create_account_if_non_existent(&coldkey, &to_hotkey);
+        SubtensorModule::stake_into_subnet(&from_hotkey, &coldkey, netuid, 10_000_000_000, 0)
+            .unwrap();
+
+        // Reset the price to 2.0
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &from_hotkey,
+            &coldkey,
+            netuid,
+        );
+        let tao_reserve = SubnetTAO::<Test>::get(netuid);
+        let alpha_reserve = SubnetAlphaIn::<Test>::get(netuid);
+        let alpha_out = SubnetAlphaOut::<Test>::get(netuid);
+        let fee_alpha = DefaultStakingFee::<Test>::get() / 2;
+        let fee_tao = SubtensorModule::sim_swap_alpha_for_tao(netuid, fee_alpha).unwrap();
+
+        System::reset_events();
+        assert_ok!(SubtensorModule::swap_hotkey_stake(
+            RuntimeOrigin::signed(coldkey),
+            from_hotkey,
+            to_hotkey,
+            netuid,
+            alpha,
+        ));
+
+        // The fee of 50_000 rao is 25_000 alpha at price 2.0. It is sold into the pool and its
+        // TAO credited back to the reserve
+        assert_eq!(SubnetTAO::<Test>::get(netuid), tao_reserve);
+        assert_eq!(
+            SubnetAlphaIn::<Test>::get(netuid),
+            alpha_reserve + fee_alpha
+        );
+        assert_eq!(
+            SubtensorModule::cumulative_fees_paid(&coldkey, &from_hotkey, netuid),
+            fee_tao
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &from_hotkey,
+                &coldkey,
+                netuid
+            ),
+            0
+        );
+        assert_abs_diff_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &to_hotkey, &coldkey, netuid
+            ),
+            alpha - fee_alpha,
+            epsilon = 10
+        );
+        assert_eq!(SubnetAlphaOut::<Test>::get(netuid), alpha_out - fee_alpha);
+        assert_eq!(StakingHotkeys::<Test>::get(coldkey), vec![to_hotkey]);
+        assert!(System::events().iter().any(|e| {
+            e.event == RuntimeEvent::SubtensorModule(Event::HotkeyExitedSubnet(from_hotkey, netuid))
+        }));
+    });
+}
@@ -7,12 +7,13 @@ use safe_math::SafeDiv;
 use substrate_fixed::traits::FromFixed;
 
 use super::mock::*;
+use crate::staking::stake_utils::ZeroLiquidityPolicy;
 use crate::*;
 use approx::assert_abs_diff_eq;
 use frame_support::dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays};
 use frame_support::sp_runtime::DispatchError;
 use sp_core::{Get, H256, U256};
-use substrate_fixed::types::{I96F32, I110F18, U64F64, U96F32};
+use substrate_fixed::types::{I64F64, I96F32, I110F18, U64F64, U96F32};
 
 /***********************************************************
     staking::add_stake() tests
@@ -661,7 +662,8 @@ fn test_remove_stake_insufficient_liquidity() {
         // Simulate stake for hotkey
         SubnetTAO::<Test>::insert(netuid, u64::MAX / 1000);
         SubnetAlphaIn::<Test>::insert(netuid, u64::MAX / 1000);
-        let alpha = SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount_staked, 0);
+        let alpha = SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount_staked, 0)
+            .unwrap();
 
         // Set the liquidity at lowest possible value so that all staking requests fail
         SubnetTAO::<Test>::insert(
@@ -2800,7 +2802,8 @@ fn test_unstake_low_liquidity_validate() {
         // Simulate stake for hotkey
         SubnetTAO::<Test>::insert(netuid, u64::MAX / 1000);
         SubnetAlphaIn::<Test>::insert(netuid, u64::MAX / 1000);
-        let alpha = SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount_staked, 0);
+        let alpha = SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount_staked, 0)
+            .unwrap();
 
         // Set the liquidity at lowest possible value so that all staking requests fail
         SubnetTAO::<Test>::insert(
@@ -3753,6 +3756,62 @@ fn test_add_stake_limit_fill_or_kill() {
     });
 }
 
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_limit_at_price_with_epsilon --exact --show-output
+#[test]
+fn test_add_stake_limit_at_price_with_epsilon() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(533453);
+        let coldkey_account_id = U256::from(55453);
+        let amount = 100_000_000;
+
+        let netuid: u16 = add_dynamic_network(&hotkey_account_id, &coldkey_account_id);
+
+        // Force-set the reserves to make price equal 1.0
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000_u64);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000_u64);
+        assert_eq!(
+            SubtensorModule::get_alpha_price(netuid),
+            U96F32::from_num(1.0)
+        );
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey_account_id, amount);
+
+        // The limit is exactly the current price
+        let limit_price = 1_000_000_000;
+
+        // With the default epsilon the comparison is strict and nothing can fill
+        assert_eq!(SubtensorModule::get_limit_price_epsilon(), 0);
+        assert_noop!(
+            SubtensorModule::add_stake_limit(
+                RuntimeOrigin::signed(coldkey_account_id),
+                hotkey_account_id,
+                netuid,
+                amount,
+                limit_price,
+                false
+            ),
+            Error::<Test>::SlippageTooHigh
+        );
+
+        // Within the epsilon the limit is satisfied and the order fills
+        SubtensorModule::set_limit_price_epsilon(10_000_000);
+        assert_ok!(SubtensorModule::add_stake_limit(
+            RuntimeOrigin::signed(coldkey_account_id),
+            hotkey_account_id,
+            netuid,
+            amount,
+            limit_price,
+            false
+        ));
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey_account_id,
+                &coldkey_account_id,
+                netuid
+            ) > 0
+        );
+    });
+}
+
 #[test]
 fn test_remove_stake_limit_ok() {
     new_test_ext(1).execute_with(|| {
@@ -4431,3 +4490,3341 @@ fn test_unstake_all_works() {
         assert!(new_balance > 100_000);
     });
 }
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_delegation_allowlist --exact --show-output
+#[test]
+fn test_add_stake_delegation_allowlist() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let allowed_coldkey = U256::from(3);
+        let other_coldkey = U256::from(4);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let netuid: u16 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        SubtensorModule::add_balance_to_coldkey_account(&allowed_coldkey, amount);
+        SubtensorModule::add_balance_to_coldkey_account(&other_coldkey, amount);
+
+        // Only the hotkey owner can set the allowlist
+        assert_noop!(
+            SubtensorModule::set_delegation_allowlist(
+                RuntimeOrigin::signed(other_coldkey),
+                owner_hotkey,
+                netuid,
+                Some(vec![allowed_coldkey].try_into().unwrap())
+            ),
+            Error::<Test>::NonAssociatedColdKey
+        );
+        assert_ok!(SubtensorModule::set_delegation_allowlist(
+            RuntimeOrigin::signed(owner_coldkey),
+            owner_hotkey,
+            netuid,
+            Some(vec![allowed_coldkey].try_into().unwrap())
+        ));
+
+        // Allowlisted coldkey can delegate
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(allowed_coldkey),
+            owner_hotkey,
+            netuid,
+            amount
+        ));
+
+        // Non-listed coldkey is rejected
+        assert_noop!(
+            SubtensorModule::add_stake(
+                RuntimeOrigin::signed(other_coldkey),
+                owner_hotkey,
+                netuid,
+                amount
+            ),
+            Error::<Test>::DelegationNotAllowed
+        );
+
+        // Clearing the allowlist lets anyone delegate again
+        assert_ok!(SubtensorModule::set_delegation_allowlist(
+            RuntimeOrigin::signed(owner_coldkey),
+            owner_hotkey,
+            netuid,
+            None
+        ));
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(other_coldkey),
+            owner_hotkey,
+            netuid,
+            amount
+        ));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_move_and_transfer_stake_delegation_allowlist --exact --show-output
+#[test]
+fn test_move_and_transfer_stake_delegation_allowlist() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let allowed_coldkey = U256::from(3);
+        let other_coldkey = U256::from(4);
+        let other_hotkey = U256::from(5);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let netuid: u16 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        SubtensorModule::create_account_if_non_existent(&other_coldkey, &other_hotkey);
+        SubtensorModule::stake_into_subnet(&other_hotkey, &other_coldkey, netuid, amount, 0)
+            .unwrap();
+        SubtensorModule::create_account_if_non_existent(&allowed_coldkey, &owner_hotkey);
+        SubtensorModule::stake_into_subnet(&owner_hotkey, &allowed_coldkey, netuid, amount, 0)
+            .unwrap();
+        assert_ok!(SubtensorModule::set_delegation_allowlist(
+            RuntimeOrigin::signed(owner_coldkey),
+            owner_hotkey,
+            netuid,
+            Some(vec![allowed_coldkey].try_into().unwrap())
+        ));
+
+        // A non-listed coldkey cannot move its stake into the allowlisted hotkey
+        let other_alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &other_hotkey,
+            &other_coldkey,
+            netuid,
+        );
+        assert_noop!(
+            SubtensorModule::move_stake(
+                RuntimeOrigin::signed(other_coldkey),
+                other_hotkey,
+                owner_hotkey,
+                netuid,
+                netuid,
+                other_alpha
+            ),
+            Error::<Test>::DelegationNotAllowed
+        );
+
+        // Nor can an allowlisted coldkey transfer its stake to a non-listed coldkey
+        let allowed_alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &allowed_coldkey,
+            netuid,
+        );
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                RuntimeOrigin::signed(allowed_coldkey),
+                other_coldkey,
+                owner_hotkey,
+                netuid,
+                netuid,
+                allowed_alpha
+            ),
+            Error::<Test>::DelegationNotAllowed
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_global_alpha_index --exact --show-output
+#[test]
+fn test_get_global_alpha_index() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid1 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(root_netuid, 1, 0);
+
+        // Shallow subnet priced at 2.0, deep subnet priced at 0.5
+        SubnetTAO::<Test>::insert(netuid1, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid1, 50_000_000_000);
+        SubnetTAO::<Test>::insert(netuid2, 300_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid2, 600_000_000_000);
+        SubnetTAO::<Test>::insert(root_netuid, 400_000_000_000);
+
+        // (2.0 * 100 + 0.5 * 300) / 400 = 0.875
+        assert_abs_diff_eq!(
+            SubtensorModule::get_global_alpha_index(false, ZeroLiquidityPolicy::Skip)
+                .to_num::<f64>(),
+            0.875,
+            epsilon = 0.000001
+        );
+
+        // Root is included at price 1.0: (200 + 150 + 400) / 800 = 0.9375
+        assert_abs_diff_eq!(
+            SubtensorModule::get_global_alpha_index(true, ZeroLiquidityPolicy::Skip)
+                .to_num::<f64>(),
+            0.9375,
+            epsilon = 0.000001
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_alpha_conservation_after_swaps --exact --show-output
+#[test]
+fn test_alpha_conservation_after_swaps() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        let issuance = SubtensorModule::get_alpha_issuance(netuid);
+        assert!(SubtensorModule::check_alpha_conservation(netuid));
+
+        // Stake in several steps
+        for amount in [1_000_000_000, 5_000_000_000, 20_000_000_000] {
+            SubtensorModule::stake_into_subnet(&owner_hotkey, &coldkey, netuid, amount, 0).unwrap();
+            assert!(SubtensorModule::check_alpha_conservation(netuid));
+            assert_eq!(SubtensorModule::get_alpha_issuance(netuid), issuance);
+        }
+
+        // Unstake half, then the rest
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &coldkey,
+            netuid,
+        );
+        SubtensorModule::unstake_from_subnet(&owner_hotkey, &coldkey, netuid, alpha / 2, 0);
+        assert!(SubtensorModule::check_alpha_conservation(netuid));
+        assert_eq!(SubtensorModule::get_alpha_issuance(netuid), issuance);
+
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &coldkey,
+            netuid,
+        );
+        SubtensorModule::unstake_from_subnet(&owner_hotkey, &coldkey, netuid, alpha, 0);
+        assert!(SubtensorModule::check_alpha_conservation(netuid));
+        assert_eq!(SubtensorModule::get_alpha_issuance(netuid), issuance);
+
+        // Pending emission is outstanding but not held yet
+        SubnetAlphaOut::<Test>::mutate(netuid, |alpha_out| *alpha_out += 1_000);
+        assert!(!SubtensorModule::check_alpha_conservation(netuid));
+        PendingEmission::<Test>::insert(netuid, 1_000);
+        assert!(SubtensorModule::check_alpha_conservation(netuid));
+
+        // Alpha held by hotkeys beyond the outstanding alpha breaks the invariant
+        TotalHotkeyAlpha::<Test>::insert(
+            owner_hotkey,
+            netuid,
+            SubnetAlphaOut::<Test>::get(netuid) + 1,
+        );
+        assert!(!SubtensorModule::check_alpha_conservation(netuid));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_hotkey_exited_subnet_event --exact --show-output
+#[test]
+fn test_hotkey_exited_subnet_event() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let coldkey = U256::from(4);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, 10_000_000_000, 0).unwrap();
+        let exited = || {
+            System::events().iter().any(|e| {
+                e.event == RuntimeEvent::SubtensorModule(Event::HotkeyExitedSubnet(hotkey, netuid))
+            })
+        };
+
+        // Partial unstake does not signal an exit
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha / 2, 0);
+        assert!(!exited());
+
+        // Unstaking the rest does
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha, 0);
+        assert_eq!(TotalHotkeyAlpha::<Test>::get(hotkey, netuid), 0);
+        assert!(exited());
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_last_stake_result --exact --show-output
+#[test]
+fn test_last_stake_result() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let fee = DefaultStakingFee::<Test>::get();
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+        assert_eq!(
+            SubtensorModule::get_last_stake_result(&coldkey, &hotkey),
+            None
+        );
+
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        assert_eq!(
+            SubtensorModule::get_last_stake_result(&coldkey, &hotkey)
+                .map(|(netuid, tao, alpha, fee, _)| (netuid, tao, alpha, fee)),
+            Some((netuid, amount - fee, alpha, fee))
+        );
+
+        // A new stake overwrites the cached result
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount / 2
+        ));
+        let (cached_netuid, cached_tao, cached_alpha, cached_fee, _) =
+            SubtensorModule::get_last_stake_result(&coldkey, &hotkey).unwrap();
+        assert_eq!(cached_netuid, netuid);
+        assert_eq!(cached_tao, amount / 2 - fee);
+        assert_abs_diff_eq!(
+            cached_alpha,
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid)
+                - alpha,
+            epsilon = 1
+        );
+        assert_eq!(cached_fee, fee);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_below_min_share_increment --exact --show-output
+#[test]
+fn test_add_stake_below_min_share_increment() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let delegator = U256::from(3);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+        SubtensorModule::add_balance_to_coldkey_account(&delegator, amount * 2);
+
+        // Default keeps the implicit behavior
+        assert_eq!(
+            SubtensorModule::get_min_share_increment(netuid),
+            U64F64::from_num(0)
+        );
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+
+        // Require more shares than the stake can mint
+        SubtensorModule::set_min_share_increment(netuid, U64F64::from_num(amount * 1_000));
+        assert_noop!(
+            SubtensorModule::add_stake(RuntimeOrigin::signed(delegator), hotkey, netuid, amount),
+            Error::<Test>::ShareIncrementTooLow
+        );
+
+        // A threshold below the minted shares accepts the stake
+        SubtensorModule::set_min_share_increment(netuid, U64F64::from_num(1));
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(delegator),
+            hotkey,
+            netuid,
+            amount
+        ));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_price_momentum --exact --show-output
+#[test]
+fn test_get_price_momentum() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let stable_netuid = 10;
+        add_network(stable_netuid, 1, 0);
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(root_netuid, 1, 0);
+
+        // Spot price 2.0
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+
+        // Uptrend: spot above the moving price
+        SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(1.5));
+        assert_abs_diff_eq!(
+            SubtensorModule::get_price_momentum(netuid).to_num::<f64>(),
+            0.5,
+            epsilon = 0.000001
+        );
+
+        // Downtrend: spot below the moving price
+        SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(2.25));
+        assert_abs_diff_eq!(
+            SubtensorModule::get_price_momentum(netuid).to_num::<f64>(),
+            -0.25,
+            epsilon = 0.000001
+        );
+
+        // Root and stable subnets have no momentum
+        SubnetMovingPrice::<Test>::insert(stable_netuid, I96F32::from_num(0.5));
+        assert_eq!(
+            SubtensorModule::get_price_momentum(stable_netuid),
+            I96F32::from_num(0)
+        );
+        assert_eq!(
+            SubtensorModule::get_price_momentum(root_netuid),
+            I96F32::from_num(0)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_coldkey_subnets --exact --show-output
+#[test]
+fn test_get_coldkey_subnets() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid1 = add_dynamic_network(&hotkey, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&hotkey, &owner_coldkey);
+        let _netuid3 = add_dynamic_network(&hotkey, &owner_coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 3);
+        assert!(SubtensorModule::get_coldkey_subnets(&coldkey).is_empty());
+
+        // Stake twice on the second subnet and once on the first
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid2,
+            amount
+        ));
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid1,
+            amount
+        ));
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid2,
+            amount
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_coldkey_subnets(&coldkey),
+            vec![netuid1, netuid2]
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_remove_stake_fee_exceeds_unstake_amount --exact --show-output
+#[test]
+fn test_remove_stake_fee_exceeds_unstake_amount() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+
+        // Dividends as large as the hotkey alpha make the dynamic fee swamp the output
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid, 1_000);
+        AlphaDividendsPerSubnet::<Test>::insert(netuid, hotkey, 2_000);
+        let dust = alpha / 5;
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+        assert_noop!(
+            SubtensorModule::remove_stake(RuntimeOrigin::signed(coldkey), hotkey, netuid, dust),
+            Error::<Test>::FeeExceedsUnstakeAmount
+        );
+
+        // Unstaking all skips the position instead of burning it
+        assert_ok!(SubtensorModule::unstake_all(
+            RuntimeOrigin::signed(coldkey),
+            hotkey
+        ));
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid),
+            alpha
+        );
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            balance_before
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_last_realized_slippage --exact --show-output
+#[test]
+fn test_last_realized_slippage() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let fee = DefaultStakingFee::<Test>::get();
+        let amount = 10_000_000_000 + fee;
+
+        // Price is 1.0 at a depth of 100 TAO
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+        assert_eq!(
+            SubtensorModule::get_last_realized_slippage(&coldkey, &hotkey),
+            U96F32::from_num(0)
+        );
+
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+
+        // 10 TAO buy 100 * 10 / 110 alpha, an execution price of 1.1 against a spot of 1.0
+        assert_abs_diff_eq!(
+            SubtensorModule::get_last_realized_slippage(&coldkey, &hotkey).to_num::<f64>(),
+            0.1,
+            epsilon = 0.000_001
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_remove_stake_tao --exact --show-output
+#[test]
+fn test_remove_stake_tao() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let tao_target = 10_000_000_000;
+
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            netuid,
+            50_000_000_000,
+        );
+
+        // The alpha needed swaps for at least the target
+        let alpha_needed = SubtensorModule::alpha_needed_for_tao(netuid, tao_target).unwrap();
+        let tao_out = SubtensorModule::sim_swap_alpha_for_tao(netuid, alpha_needed).unwrap();
+        assert!(tao_out >= tao_target);
+        assert_abs_diff_eq!(tao_out, tao_target, epsilon = 2);
+
+        // Unstake 10 TAO worth and receive 10 TAO minus the fee
+        let fee = SubtensorModule::calculate_staking_fee(
+            Some((&hotkey, netuid)),
+            &coldkey,
+            None,
+            &coldkey,
+            U96F32::from_num(alpha_needed),
+        );
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+        assert_ok!(SubtensorModule::remove_stake_tao(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            tao_target,
+            false
+        ));
+        assert_abs_diff_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey) - balance_before,
+            tao_target - fee,
+            epsilon = 2
+        );
+        let alpha_left =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        assert_abs_diff_eq!(alpha_left, 50_000_000_000 - alpha_needed, epsilon = 1);
+
+        // A target worth more than the stake is rejected unless partial execution is allowed
+        assert_noop!(
+            SubtensorModule::remove_stake_tao(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                60_000_000_000,
+                false
+            ),
+            Error::<Test>::NotEnoughStakeToWithdraw
+        );
+
+        // So is a target the pool cannot pay out
+        assert_noop!(
+            SubtensorModule::remove_stake_tao(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                2_000_000_000_000,
+                false
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        // With partial execution the full stake is unstaked instead
+        assert_ok!(SubtensorModule::remove_stake_tao(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            2_000_000_000_000,
+            true
+        ));
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid),
+            0
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_remove_stake_fee_disproportionate --exact --show-output
+#[test]
+fn test_remove_stake_fee_disproportionate() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        SubnetMechanism::<Test>::insert(netuid, 0);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, 10_000_000,
+        );
+
+        // On a stable subnet the unstake fee is 5 * 50_000, half of a 500_001 TAO unstake
+        SubtensorModule::set_unstake_fee_multiplier(netuid, U96F32::from_num(5));
+        let alpha = DefaultMinStake::<Test>::get() + 1;
+        assert!(!SubtensorModule::fee_is_disproportionate(250_000, alpha));
+
+        // With a 25% cap the unstake is rejected
+        SubtensorModule::set_max_fee_share_bps(2_500);
+        assert!(SubtensorModule::fee_is_disproportionate(250_000, alpha));
+        assert_noop!(
+            SubtensorModule::remove_stake(RuntimeOrigin::signed(coldkey), hotkey, netuid, alpha),
+            Error::<Test>::FeeDisproportionate
+        );
+
+        // By default the fee floor is always charged
+        SubtensorModule::set_max_fee_share_bps(0);
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            alpha
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            balance_before + alpha - 250_000
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_depth_to_price --exact --show-output
+#[test]
+fn test_depth_to_price() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let stable_netuid = 10;
+        add_network(stable_netuid, 1, 0);
+
+        // Price 1.0 with k = 1e22
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+
+        // Current price has no depth
+        assert_eq!(
+            SubtensorModule::depth_to_price(netuid, 1_000_000_000),
+            (0, 0)
+        );
+
+        // Up to 4.0: the TAO reserve doubles and the alpha reserve halves
+        assert_eq!(
+            SubtensorModule::depth_to_price(netuid, 4_000_000_000),
+            (100_000_000_000, 50_000_000_000)
+        );
+
+        // Down to 0.25: the TAO reserve halves and the alpha reserve doubles
+        assert_eq!(
+            SubtensorModule::depth_to_price(netuid, 250_000_000),
+            (50_000_000_000, 100_000_000_000)
+        );
+
+        // Swapping the computed TAO in moves the price to the target
+        let (tao, _) = SubtensorModule::depth_to_price(netuid, 2_250_000_000);
+        SubtensorModule::swap_tao_for_alpha(netuid, tao);
+        assert_abs_diff_eq!(
+            SubtensorModule::get_alpha_price(netuid).to_num::<f64>(),
+            2.25,
+            epsilon = 0.000001
+        );
+
+        // Stable subnets and a zero target have no depth
+        assert_eq!(
+            SubtensorModule::depth_to_price(stable_netuid, 4_000_000_000),
+            (0, 0)
+        );
+        assert_eq!(SubtensorModule::depth_to_price(netuid, 0), (0, 0));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_fee_credited_to_reserves_event --exact --show-output
+#[test]
+fn test_fee_credited_to_reserves_event() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let fee = DefaultStakingFee::<Test>::get();
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+        let fee_credited = |amount: u64| {
+            System::events().iter().any(|e| {
+                e.event
+                    == RuntimeEvent::SubtensorModule(Event::FeeCreditedToReserves(netuid, amount))
+            })
+        };
+
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+        assert!(fee_credited(fee));
+
+        // No event when the fee is zero
+        System::reset_events();
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha / 2, 0);
+        assert!(!System::events().iter().any(|e| matches!(
+            e.event,
+            RuntimeEvent::SubtensorModule(Event::FeeCreditedToReserves(..))
+        )));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_historical_hotkey_alpha --exact --show-output
+#[test]
+fn test_historical_hotkey_alpha() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        Keys::<Test>::insert(netuid, 0, hotkey);
+
+        // Snapshots are off by default
+        TotalHotkeyAlpha::<Test>::insert(hotkey, netuid, 1_000);
+        SubtensorModule::snapshot_hotkey_alpha(netuid);
+        assert_eq!(
+            SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, 0),
+            None
+        );
+
+        // Two epochs with different alpha
+        SubtensorModule::set_hotkey_alpha_snapshot_retention(30);
+        SubtensorModule::snapshot_hotkey_alpha(netuid);
+        TotalHotkeyAlpha::<Test>::insert(hotkey, netuid, 2_500);
+        SubtensorModule::snapshot_hotkey_alpha(netuid);
+
+        assert_eq!(
+            SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, 0),
+            Some(1_000)
+        );
+        assert_eq!(
+            SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, 1),
+            Some(2_500)
+        );
+        assert_eq!(
+            SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, 2),
+            None
+        );
+
+        // The oldest epoch is pruned beyond the retention
+        SubtensorModule::set_hotkey_alpha_snapshot_retention(2);
+        SubtensorModule::snapshot_hotkey_alpha(netuid);
+        assert_eq!(
+            SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, 0),
+            None
+        );
+        assert_eq!(
+            SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, 2),
+            Some(2_500)
+        );
+
+        // Disabling the snapshots prunes the retained epochs
+        SubtensorModule::set_hotkey_alpha_snapshot_retention(0);
+        assert!(!SubtensorModule::snapshot_hotkey_alpha(netuid).is_zero());
+        for epoch in 0..3 {
+            assert_eq!(
+                SubtensorModule::get_historical_hotkey_alpha(&hotkey, netuid, epoch),
+                None
+            );
+        }
+        assert_eq!(HotkeyAlphaSnapshotPrunedUntil::<Test>::get(netuid), 3);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_stake_fee_burn --exact --show-output
+#[test]
+fn test_stake_fee_burn() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let fee = DefaultStakingFee::<Test>::get();
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount);
+
+        // Burn 30% of the fee
+        SubtensorModule::set_fee_burn_bps(netuid, 3_000);
+        let burned = fee * 3 / 10;
+        assert_eq!(SubtensorModule::get_fee_burn_amount(netuid, fee), burned);
+
+        let issuance_before = TotalIssuance::<Test>::get();
+        let stake_before = TotalStake::<Test>::get();
+        let subnet_tao_before = SubnetTAO::<Test>::get(netuid);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+
+        // The burned portion reduces the issuance, the rest goes to the reserves
+        assert_eq!(TotalIssuance::<Test>::get(), issuance_before - burned);
+        assert_eq!(
+            SubnetTAO::<Test>::get(netuid),
+            subnet_tao_before + amount - burned
+        );
+        assert_eq!(TotalStake::<Test>::get(), stake_before + amount - burned);
+        assert!(System::events().iter().any(|e| {
+            e.event == RuntimeEvent::SubtensorModule(Event::StakingFeeBurned(netuid, burned))
+        }));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_tao_weight_contribution --exact --show-output
+#[test]
+fn test_get_tao_weight_contribution() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(root_netuid, 1, 0);
+
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, 1_000_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            root_netuid,
+            4_000_000,
+        );
+
+        // Half of the tao stake is counted
+        SubtensorModule::set_tao_weight(u64::MAX / 2);
+        let contribution = SubtensorModule::get_tao_weight_contribution(&hotkey, netuid);
+        assert_abs_diff_eq!(contribution.to_num::<f64>(), 2_000_000.0, epsilon = 1.0);
+
+        let (total_stake, alpha_stake, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey, netuid);
+        assert_eq!(contribution + alpha_stake, total_stake);
+
+        // No contribution without tao weight
+        SubtensorModule::set_tao_weight(0);
+        assert_eq!(
+            SubtensorModule::get_tao_weight_contribution(&hotkey, netuid),
+            I64F64::from_num(0)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_stake_weights_with_tao_weight --exact --show-output
+#[test]
+fn test_get_stake_weights_with_tao_weight() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(netuid, 1, 0);
+        add_network(root_netuid, 1, 0);
+        for i in 0..3_u64 {
+            let hotkey = U256::from(10 + i);
+            let coldkey = U256::from(20 + i);
+            register_ok_neuron(netuid, hotkey, coldkey, i);
+            SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                netuid,
+                1_000_000 * (i + 1),
+            );
+            SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                root_netuid,
+                3_000_000 * (3 - i),
+            );
+        }
+        let stored_weight = u64::MAX / 4;
+        SubtensorModule::set_tao_weight(stored_weight);
+
+        // Overriding with the stored weight yields identical output
+        let (total_stake, _, _) = SubtensorModule::get_stake_weights_for_network(netuid);
+        assert_eq!(
+            SubtensorModule::get_stake_weights_with_tao_weight(netuid, stored_weight),
+            total_stake
+        );
+
+        // A zero override leaves only the alpha stake
+        let (_, alpha_stake, _) = SubtensorModule::get_stake_weights_for_network(netuid);
+        assert_eq!(
+            SubtensorModule::get_stake_weights_with_tao_weight(netuid, 0),
+            alpha_stake
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_simulate_tao_weight_change --exact --show-output
+#[test]
+fn test_simulate_tao_weight_change() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(netuid, 1, 0);
+        add_network(root_netuid, 1, 0);
+        for i in 0..3_u64 {
+            let hotkey = U256::from(10 + i);
+            let coldkey = U256::from(20 + i);
+            register_ok_neuron(netuid, hotkey, coldkey, i);
+            SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                netuid,
+                1_000_000 * (i + 1),
+            );
+            SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                root_netuid,
+                3_000_000 * (3 - i),
+            );
+        }
+        SubtensorModule::set_tao_weight(u64::MAX / 4);
+
+        let simulation = SubtensorModule::simulate_tao_weight_change(netuid, u64::MAX / 4 * 3);
+        assert_eq!(simulation.len(), 3);
+
+        // The current weights are reported unchanged
+        let (total_stake, _, _) = SubtensorModule::get_stake_weights_for_network(netuid);
+        for (uid, old, _) in simulation.iter() {
+            assert_eq!(Some(old), total_stake.get(*uid as usize));
+        }
+
+        // UID 0 holds the most tao stake and gains the most: 1 + 9 * 0.75 = 7.75 from
+        // 1 + 9 * 0.25 = 3.25, overtaking UID 2 at 3 + 3 * 0.75 = 5.25 from 3.75
+        let (_, old_0, new_0) = *simulation.first().unwrap();
+        let (_, old_2, new_2) = *simulation.get(2).unwrap();
+        assert!(old_0 < old_2);
+        assert!(new_0 > new_2);
+        assert!(new_0 / old_0 > new_2 / old_2);
+        assert_abs_diff_eq!(new_0.to_num::<f64>(), 7_750_000.0, epsilon = 1.0);
+        assert_abs_diff_eq!(new_2.to_num::<f64>(), 5_250_000.0, epsilon = 1.0);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_subnet_tao_weight_override --exact --show-output
+#[test]
+fn test_subnet_tao_weight_override() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let other_netuid: u16 = 2;
+        let root_netuid = SubtensorModule::get_root_netuid();
+        let hotkey = U256::from(10);
+        let coldkey = U256::from(20);
+        add_network(netuid, 1, 0);
+        add_network(other_netuid, 1, 0);
+        add_network(root_netuid, 1, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        register_ok_neuron(other_netuid, hotkey, coldkey, 1);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, 1_000_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            other_netuid,
+            1_000_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            root_netuid,
+            4_000_000,
+        );
+        SubtensorModule::set_tao_weight(u64::MAX / 4);
+
+        // Under the global weight: 1 + 4 * 0.25 = 2
+        let (global_weight, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey, netuid);
+        assert_abs_diff_eq!(global_weight.to_num::<f64>(), 2_000_000.0, epsilon = 1.0);
+
+        // Under the subnet weight: 1 + 4 * 0.75 = 4
+        SubtensorModule::set_subnet_tao_weight(netuid, Some(u64::MAX / 4 * 3));
+        let (subnet_weight, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey, netuid);
+        assert_abs_diff_eq!(subnet_weight.to_num::<f64>(), 4_000_000.0, epsilon = 1.0);
+        let (total_stake, _, _) = SubtensorModule::get_stake_weights_for_network(netuid);
+        assert_eq!(total_stake.first(), Some(&subnet_weight));
+
+        // Other subnets keep the global weight
+        let (other_weight, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey, other_netuid);
+        assert_eq!(other_weight, global_weight);
+
+        // Clearing the override restores the global weight
+        SubtensorModule::set_subnet_tao_weight(netuid, None);
+        let (cleared_weight, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey, netuid);
+        assert_eq!(cleared_weight, global_weight);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_preview_limit_fill --exact --show-output
+#[test]
+fn test_preview_limit_fill() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Price 1.0
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+
+        // Buying up to a price of 2.0 fills 100 TAO
+        assert_eq!(
+            SubtensorModule::preview_limit_fill(netuid, 50_000_000_000, 2_000_000_000, true),
+            (50_000_000_000, 0)
+        );
+        assert_eq!(
+            SubtensorModule::preview_limit_fill(netuid, 150_000_000_000, 2_000_000_000, true),
+            (100_000_000_000, 50_000_000_000)
+        );
+        assert_eq!(
+            SubtensorModule::preview_limit_fill(netuid, 150_000_000_000, 500_000_000, true),
+            (0, 150_000_000_000)
+        );
+
+        // Selling down to a price of 0.5 fills 100 alpha
+        assert_eq!(
+            SubtensorModule::preview_limit_fill(netuid, 50_000_000_000, 500_000_000, false),
+            (50_000_000_000, 0)
+        );
+        assert_eq!(
+            SubtensorModule::preview_limit_fill(netuid, 150_000_000_000, 500_000_000, false),
+            (100_000_000_000, 50_000_000_000)
+        );
+        assert_eq!(
+            SubtensorModule::preview_limit_fill(netuid, 150_000_000_000, 2_000_000_000, false),
+            (0, 150_000_000_000)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_estimate_delegator_apy --exact --show-output
+#[test]
+fn test_estimate_delegator_apy() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&hotkey, &owner_coldkey);
+        SubtensorModule::set_tempo(netuid, 99);
+        SubnetTAO::<Test>::insert(netuid, 10_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 10_000_000_000_000);
+        assert_eq!(
+            SubtensorModule::estimate_delegator_apy(&hotkey, &coldkey, netuid),
+            U96F32::from_num(0)
+        );
+
+        let stake = 1_000_000_000;
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, stake,
+        );
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid, 1_000_000_000_000);
+        AlphaDividendsPerSubnet::<Test>::insert(netuid, hotkey, 1_000_000);
+
+        // 0.0001% per epoch over 26280 epochs a year, minus one unstake fee
+        let stake_tao = SubtensorModule::sim_swap_alpha_for_tao(netuid, stake).unwrap();
+        let fee = DefaultStakingFee::<Test>::get();
+        let expected = 0.000_001 * 26_280.0 - fee as f64 / stake_tao as f64;
+        assert_abs_diff_eq!(
+            SubtensorModule::estimate_delegator_apy(&hotkey, &coldkey, netuid).to_num::<f64>(),
+            expected,
+            epsilon = 0.000_001
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_cooldown --exact --show-output
+#[test]
+fn test_add_stake_cooldown() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 3);
+        SubtensorModule::set_stake_cooldown_blocks(netuid, 10);
+
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+
+        // Second stake within the cooldown is rejected
+        step_block(9);
+        assert_noop!(
+            SubtensorModule::add_stake(RuntimeOrigin::signed(coldkey), hotkey, netuid, amount),
+            Error::<Test>::StakeCooldownNotElapsed
+        );
+
+        // Once the cooldown has elapsed the stake goes through
+        step_block(1);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_moving_price_scaled --exact --show-output
+#[test]
+fn test_get_moving_price_scaled() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let moving_price = I96F32::from_num(1.234_567_891);
+        SubnetMovingPrice::<Test>::insert(netuid, moving_price);
+
+        let scale = 1_000_000_000;
+        assert_eq!(
+            SubtensorModule::get_moving_price_scaled(netuid, scale),
+            (moving_price * I96F32::from_num(scale)).to_num::<u64>()
+        );
+        assert_eq!(SubtensorModule::get_moving_price_scaled(netuid, 1), 1);
+
+        // Negative prices are clamped to zero
+        SubnetMovingPrice::<Test>::insert(netuid, I96F32::from_num(-0.5));
+        assert_eq!(SubtensorModule::get_moving_price_scaled(netuid, scale), 0);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_reduce_all_positions --exact --show-output
+#[test]
+fn test_reduce_all_positions() {
+    new_test_ext(1).execute_with(|| {
+        let owner_coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let coldkey = U256::from(4);
+        let amount = 100_000_000_000;
+        let netuid1 = add_dynamic_network(&hotkey1, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&hotkey2, &owner_coldkey);
+        for netuid in [netuid1, netuid2] {
+            SubnetTAO::<Test>::insert(netuid, 10_000_000_000_000);
+            SubnetAlphaIn::<Test>::insert(netuid, 10_000_000_000_000);
+        }
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey1,
+            netuid1,
+            amount
+        ));
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey2,
+            netuid2,
+            amount
+        ));
+        let alpha1 = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey1, &coldkey, netuid1,
+        );
+        let alpha2 = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey2, &coldkey, netuid2,
+        );
+
+        // More than 100% is rejected
+        assert_noop!(
+            SubtensorModule::reduce_all_positions(RuntimeOrigin::signed(coldkey), 10_001),
+            Error::<Test>::InvalidReduceBps
+        );
+
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+        assert_ok!(SubtensorModule::reduce_all_positions(
+            RuntimeOrigin::signed(coldkey),
+            5_000
+        ));
+
+        // Both positions are halved
+        assert_abs_diff_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey1, &coldkey, netuid1
+            ),
+            alpha1 - alpha1 / 2,
+            epsilon = 10
+        );
+        assert_abs_diff_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey2, &coldkey, netuid2
+            ),
+            alpha2 - alpha2 / 2,
+            epsilon = 10
+        );
+
+        // The recovered TAO is credited and reported
+        let recovered = SubtensorModule::get_coldkey_balance(&coldkey) - balance_before;
+        assert!(recovered > 0);
+        assert!(System::events().iter().any(|e| {
+            e.event
+                == RuntimeEvent::SubtensorModule(Event::PositionsReduced(coldkey, 5_000, recovered))
+        }));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_reduce_all_positions_too_many_positions --exact --show-output
+#[test]
+fn test_reduce_all_positions_too_many_positions() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        StakingHotkeys::<Test>::insert(coldkey, vec![hotkey]);
+        for netuid in 0..MAX_REDUCE_POSITIONS as u16 {
+            Alpha::<Test>::insert((hotkey, coldkey, netuid), U64F64::from_num(1));
+        }
+        assert_ok!(SubtensorModule::reduce_all_positions(
+            RuntimeOrigin::signed(coldkey),
+            5_000
+        ));
+
+        // One position more than a call may reduce is rejected
+        Alpha::<Test>::insert(
+            (hotkey, coldkey, MAX_REDUCE_POSITIONS as u16),
+            U64F64::from_num(1),
+        );
+        assert_noop!(
+            SubtensorModule::reduce_all_positions(RuntimeOrigin::signed(coldkey), 5_000),
+            Error::<Test>::TooManyPositions
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_breakeven_tao_weight --exact --show-output
+#[test]
+fn test_breakeven_tao_weight() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_a = U256::from(1);
+        let hotkey_b = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&hotkey_a, &coldkey);
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(root_netuid, 1, 0);
+
+        // A: 1M alpha and 4M tao, B: 3M alpha and no tao. Equal at a tao weight of 0.5.
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_a, &coldkey, netuid, 1_000_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_a,
+            &coldkey,
+            root_netuid,
+            4_000_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_b, &coldkey, netuid, 3_000_000,
+        );
+
+        let weight = SubtensorModule::breakeven_tao_weight(&hotkey_a, &hotkey_b, netuid).unwrap();
+        assert_abs_diff_eq!(
+            SubtensorModule::normalize_tao_weight(weight).to_num::<f64>(),
+            0.5,
+            epsilon = 0.000_001
+        );
+
+        // At the breakeven weight both hotkeys have the same weighted stake
+        SubtensorModule::set_tao_weight(weight);
+        let (total_a, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey_a, netuid);
+        let (total_b, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey_b, netuid);
+        assert_abs_diff_eq!(
+            total_a.to_num::<f64>(),
+            total_b.to_num::<f64>(),
+            epsilon = 1.0
+        );
+
+        // B never overtakes A once A also leads on alpha
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_a, &coldkey, netuid, 3_000_000,
+        );
+        assert_eq!(
+            SubtensorModule::breakeven_tao_weight(&hotkey_a, &hotkey_b, netuid),
+            None
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_min_pool_liquidity_override --exact --show-output
+#[test]
+fn test_min_pool_liquidity_override() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let alpha = 100_000_000;
+        SubnetTAO::<Test>::insert(netuid, 100_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, alpha,
+        );
+
+        // Unset, the global default applies
+        assert_eq!(
+            SubtensorModule::get_min_pool_liquidity(netuid),
+            DefaultMinimumPoolLiquidity::<Test>::get()
+        );
+
+        // The unstake would leave 50_000_000 TAO in the pool, below the override
+        SubtensorModule::set_min_pool_liquidity_override(netuid, Some(60_000_000));
+        assert_noop!(
+            SubtensorModule::remove_stake(RuntimeOrigin::signed(coldkey), hotkey, netuid, alpha),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        // Cleared, the unstake succeeds under the global default
+        SubtensorModule::set_min_pool_liquidity_override(netuid, None);
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            alpha
+        ));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_trade_spread --exact --show-output
+#[test]
+fn test_get_trade_spread() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+
+        // The spread grows with the trade size
+        let small = SubtensorModule::get_trade_spread(&hotkey, netuid, 1_000_000_000);
+        let medium = SubtensorModule::get_trade_spread(&hotkey, netuid, 10_000_000_000);
+        let large = SubtensorModule::get_trade_spread(&hotkey, netuid, 100_000_000_000);
+        assert!(small > U96F32::from_num(0));
+        assert!(small < medium);
+        assert!(medium < large);
+
+        // The sell leg pays the subnet's unstake fee multiplier
+        SubtensorModule::set_unstake_fee_multiplier(netuid, U96F32::from_num(5));
+        assert!(SubtensorModule::get_trade_spread(&hotkey, netuid, 100_000_000_000) > large);
+        SubtensorModule::set_unstake_fee_multiplier(netuid, U96F32::from_num(1));
+
+        // Both legs are fee free during a fee holiday
+        SubtensorModule::set_fee_holiday_until(netuid, u64::MAX);
+        assert!(SubtensorModule::get_trade_spread(&hotkey, netuid, 100_000_000_000) < large);
+        FeeHolidayUntil::<Test>::remove(netuid);
+
+        // A trade that cannot execute loses everything
+        SubnetTAO::<Test>::insert(netuid, 0);
+        assert_eq!(
+            SubtensorModule::get_trade_spread(&hotkey, netuid, 1_000_000_000),
+            U96F32::from_num(1)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_staking_hotkeys_on_subnet --exact --show-output
+#[test]
+fn test_get_staking_hotkeys_on_subnet() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey1 = U256::from(1);
+        let hotkey2 = U256::from(2);
+        let owner_coldkey = U256::from(3);
+        let coldkey = U256::from(4);
+        let netuid1 = add_dynamic_network(&hotkey1, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&hotkey2, &owner_coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey1,
+            netuid1,
+            amount
+        ));
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey2,
+            netuid2,
+            amount
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_staking_hotkeys(&coldkey),
+            vec![hotkey1, hotkey2]
+        );
+
+        // Only the hotkey with stake on the queried subnet is returned
+        assert_eq!(
+            SubtensorModule::get_staking_hotkeys_on_subnet(&coldkey, netuid1),
+            vec![hotkey1]
+        );
+        assert_eq!(
+            SubtensorModule::get_staking_hotkeys_on_subnet(&coldkey, netuid2),
+            vec![hotkey2]
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_full_unstake_prunes_staking_hotkeys --exact --show-output
+#[test]
+fn test_full_unstake_prunes_staking_hotkeys() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid1 = add_dynamic_network(&hotkey, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&hotkey, &owner_coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+        for netuid in [netuid1, netuid2] {
+            assert_ok!(SubtensorModule::add_stake(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                amount
+            ));
+        }
+        assert_eq!(StakingHotkeys::<Test>::get(coldkey), vec![hotkey]);
+
+        // The hotkey stays listed while stake remains on another subnet
+        let alpha1 =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1);
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid1,
+            alpha1
+        ));
+        assert_eq!(StakingHotkeys::<Test>::get(coldkey), vec![hotkey]);
+
+        // Fully unstaking everywhere removes it
+        let alpha2 =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid2);
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid2,
+            alpha2
+        ));
+        assert!(StakingHotkeys::<Test>::get(coldkey).is_empty());
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_weighted_median_price --exact --show-output
+#[test]
+fn test_get_weighted_median_price() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid1 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let netuid3 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Prices 1.0, 1.1 and an outlier at 100.0
+        SubnetTAO::<Test>::insert(netuid1, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid1, 100_000_000_000);
+        SubnetTAO::<Test>::insert(netuid2, 110_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid2, 100_000_000_000);
+        SubnetTAO::<Test>::insert(netuid3, 50_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid3, 500_000_000);
+
+        // Half of the 260 TAO in reserves is reached at the second subnet
+        assert_abs_diff_eq!(
+            SubtensorModule::get_weighted_median_price(ZeroLiquidityPolicy::Skip).to_num::<f64>(),
+            1.1,
+            epsilon = 0.000001
+        );
+
+        // The outlier pulls the mean far above the median
+        assert!(
+            SubtensorModule::get_global_alpha_index(false, ZeroLiquidityPolicy::Skip)
+                > U96F32::from_num(10)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_inject_liquidity --exact --show-output
+#[test]
+fn test_inject_liquidity() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubtensorModule::add_balance_to_coldkey_account(&owner_coldkey, 100_000_000_000);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &owner_coldkey,
+            netuid,
+            15_000_000_000,
+        );
+        SubnetAlphaOut::<Test>::insert(netuid, 15_000_000_000);
+        let price_before = SubtensorModule::get_alpha_price(netuid);
+
+        // Only the subnet owner may inject
+        assert_noop!(
+            SubtensorModule::inject_liquidity(
+                RuntimeOrigin::signed(U256::from(3)),
+                netuid,
+                20_000_000_000,
+                10_000_000_000
+            ),
+            DispatchError::BadOrigin
+        );
+
+        // Unbalanced amounts would move the price
+        assert_noop!(
+            SubtensorModule::inject_liquidity(
+                RuntimeOrigin::signed(owner_coldkey),
+                netuid,
+                20_000_000_000,
+                20_000_000_000
+            ),
+            Error::<Test>::LiquidityInjectionChangesPrice
+        );
+
+        // The alpha must come from the owner's stake
+        assert_noop!(
+            SubtensorModule::inject_liquidity(
+                RuntimeOrigin::signed(owner_coldkey),
+                netuid,
+                40_000_000_000,
+                20_000_000_000
+            ),
+            Error::<Test>::NotEnoughStakeToWithdraw
+        );
+
+        // Balanced injection keeps the price
+        assert_ok!(SubtensorModule::inject_liquidity(
+            RuntimeOrigin::signed(owner_coldkey),
+            netuid,
+            20_000_000_000,
+            10_000_000_000
+        ));
+        assert_eq!(SubnetTAO::<Test>::get(netuid), 220_000_000_000);
+        assert_eq!(SubnetAlphaIn::<Test>::get(netuid), 110_000_000_000);
+        assert_eq!(SubnetAlphaOut::<Test>::get(netuid), 5_000_000_000);
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &owner_hotkey,
+                &owner_coldkey,
+                netuid
+            ),
+            5_000_000_000
+        );
+        assert_eq!(SubtensorModule::get_alpha_price(netuid), price_before);
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&owner_coldkey),
+            80_000_000_000
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_subnet_tvl --exact --show-output
+#[test]
+fn test_get_subnet_tvl() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Price 2.0 with 50 alpha outstanding: 2.0 * 50 + 200 = 300 TAO
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaOut::<Test>::insert(netuid, 50_000_000_000);
+        assert_eq!(SubtensorModule::get_subnet_tvl(netuid), 300_000_000_000);
+
+        // Without outstanding alpha only the reserve counts
+        SubnetAlphaOut::<Test>::insert(netuid, 0);
+        assert_eq!(SubtensorModule::get_subnet_tvl(netuid), 200_000_000_000);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_simulate_split_execution --exact --show-output
+#[test]
+fn test_simulate_split_execution() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let tao_reserve = 100_000_000_000_u64;
+        let alpha_reserve = 100_000_000_000_u64;
+        SubnetTAO::<Test>::insert(netuid, tao_reserve);
+        SubnetAlphaIn::<Test>::insert(netuid, alpha_reserve);
+
+        let total_tao = 50_000_000_000;
+        let path = SubtensorModule::simulate_split_execution(netuid, total_tao, 5);
+        assert_eq!(path.len(), 5);
+        assert!(path.windows(2).all(|w| w.first() < w.last()));
+
+        // The path ends at the price of a single full-size swap
+        let alpha_out = SubtensorModule::sim_swap_tao_for_alpha(netuid, total_tao).unwrap();
+        let single_swap_price =
+            (tao_reserve + total_tao) as f64 / (alpha_reserve - alpha_out) as f64;
+        assert_abs_diff_eq!(
+            path.last().unwrap().to_num::<f64>(),
+            single_swap_price,
+            epsilon = 0.000_001
+        );
+
+        // Nothing is written to storage
+        assert_eq!(SubnetTAO::<Test>::get(netuid), tao_reserve);
+        assert_eq!(SubnetAlphaIn::<Test>::get(netuid), alpha_reserve);
+        assert!(SubtensorModule::simulate_split_execution(netuid, total_tao, 0).is_empty());
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_limit_includes_fee --exact --show-output
+#[test]
+fn test_add_stake_limit_includes_fee() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+        let amount = 1_000_000_000;
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+
+        // The swap price of 1 TAO is exactly 1.001, the price net of the fee is above that
+        let limit_price = 1_001_000_000;
+        let (_, max_net) = SubtensorModule::get_amount_range_add_net_of_fee(
+            netuid,
+            limit_price,
+            DefaultStakingFee::<Test>::get(),
+        );
+        assert!(max_net < amount);
+        assert_noop!(
+            SubtensorModule::add_stake_limit_with_fee_option(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                amount,
+                limit_price,
+                false,
+                true
+            ),
+            Error::<Test>::SlippageTooHigh
+        );
+
+        // Checked against the swap price the order fills
+        assert_ok!(SubtensorModule::add_stake_limit_with_fee_option(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount,
+            limit_price,
+            false,
+            false
+        ));
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid)
+                > 0
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_fee_percentage --exact --show-output
+#[test]
+fn test_get_fee_percentage() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let origin_netuid = add_dynamic_network(&hotkey, &coldkey);
+        let destination_netuid = add_dynamic_network(&hotkey, &coldkey);
+        for netuid in [origin_netuid, destination_netuid] {
+            SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+            SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+        }
+        let alpha = 10_000_000_000;
+        let value = SubtensorModule::sim_swap_alpha_for_tao(origin_netuid, alpha).unwrap();
+
+        // Moving within the subnet pays the default fee
+        let default_percentage = SubtensorModule::get_fee_percentage(
+            &hotkey,
+            origin_netuid,
+            Some((&hotkey, origin_netuid)),
+            alpha,
+        );
+        assert_abs_diff_eq!(
+            default_percentage.to_num::<f64>(),
+            DefaultStakingFee::<Test>::get() as f64 / value as f64,
+            epsilon = 0.000_000_1
+        );
+
+        // With 10% dividends last epoch, a cross-subnet move pays the dynamic fee
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, origin_netuid, 100_000_000_000);
+        AlphaDividendsPerSubnet::<Test>::insert(origin_netuid, hotkey, 10_000_000_000);
+        let dynamic_percentage = SubtensorModule::get_fee_percentage(
+            &hotkey,
+            origin_netuid,
+            Some((&hotkey, destination_netuid)),
+            alpha,
+        );
+        assert_abs_diff_eq!(dynamic_percentage.to_num::<f64>(), 0.1, epsilon = 0.001);
+        assert!(dynamic_percentage > default_percentage * U96F32::from_num(100));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_alpha_market_cap --exact --show-output
+#[test]
+fn test_get_alpha_market_cap() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Price 2.0 with 150 alpha issued: 2.0 * (100 + 50) = 300 TAO
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaOut::<Test>::insert(netuid, 50_000_000_000);
+        assert_eq!(
+            SubtensorModule::get_alpha_market_cap(netuid),
+            300_000_000_000
+        );
+
+        // Stable subnets are priced at 1.0
+        SubnetMechanism::<Test>::insert(netuid, 0);
+        assert_eq!(
+            SubtensorModule::get_alpha_market_cap(netuid),
+            150_000_000_000
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_swaps_emit_reserves_changed --exact --show-output
+#[test]
+fn test_swaps_emit_reserves_changed() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaOut::<Test>::insert(netuid, 10_000_000_000);
+
+        let reserves_changed = || {
+            Event::ReservesChanged(
+                netuid,
+                SubnetTAO::<Test>::get(netuid),
+                SubnetAlphaIn::<Test>::get(netuid),
+                SubnetAlphaOut::<Test>::get(netuid),
+            )
+        };
+
+        // Buying alpha reports the post-swap reserves
+        let alpha = SubtensorModule::swap_tao_for_alpha(netuid, 1_000_000_000);
+        assert!(alpha > 0);
+        assert!(
+            System::events()
+                .iter()
+                .any(|e| { e.event == RuntimeEvent::SubtensorModule(reserves_changed()) })
+        );
+
+        // So does selling it back
+        let tao = SubtensorModule::swap_alpha_for_tao(netuid, alpha);
+        assert!(tao > 0);
+        assert_eq!(
+            System::events().last().map(|e| e.event.clone()),
+            Some(RuntimeEvent::SubtensorModule(reserves_changed()))
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_alpha_needed_for_target_weight --exact --show-output
+#[test]
+fn test_alpha_needed_for_target_weight() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(root_netuid, 1, 0);
+
+        // 1M alpha and 4M tao at a tao weight of 0.5: weight 3M
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, 1_000_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            root_netuid,
+            4_000_000,
+        );
+        SubtensorModule::set_tao_weight(u64::MAX / 2);
+
+        let needed = SubtensorModule::alpha_needed_for_target_weight(
+            &hotkey,
+            netuid,
+            I64F64::from_num(5_000_000),
+        );
+        assert_abs_diff_eq!(needed, 2_000_000, epsilon = 1);
+
+        // Staking the estimate reaches the target
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, needed,
+        );
+        let (total, _, _) =
+            SubtensorModule::get_stake_weights_for_hotkey_on_subnet(&hotkey, netuid);
+        assert!(total >= I64F64::from_num(5_000_000));
+
+        // Nothing is needed once at or above the target
+        assert_eq!(
+            SubtensorModule::alpha_needed_for_target_weight(
+                &hotkey,
+                netuid,
+                I64F64::from_num(4_000_000),
+            ),
+            0
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_limit_force_partial_fills --exact --show-output
+#[test]
+fn test_add_stake_limit_force_partial_fills() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey_account_id = U256::from(533453);
+        let coldkey_account_id = U256::from(55453);
+        let amount = 900_000_000_000; // over the maximum
+
+        // add network
+        let netuid: u16 = add_dynamic_network(&hotkey_account_id, &coldkey_account_id);
+
+        // Force-set alpha in and tao reserve to make price equal 1.5
+        SubnetTAO::<Test>::insert(netuid, 150_000_000_000_u64);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000_u64);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey_account_id, amount);
+
+        // Only 450 TAO can be executed below this limit price
+        let limit_price = 6_000_000_000;
+        SubtensorModule::set_force_partial_fills(netuid, true);
+
+        // The fill or kill order is coerced to a partial fill
+        assert_ok!(SubtensorModule::add_stake_limit(
+            RuntimeOrigin::signed(coldkey_account_id),
+            hotkey_account_id,
+            netuid,
+            amount,
+            limit_price,
+            false
+        ));
+        assert_abs_diff_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey_account_id),
+            amount - 450_000_000_000,
+            epsilon = amount / 1_000
+        );
+        assert!(SubtensorModule::get_alpha_price(netuid) <= U96F32::from_num(6.0));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_pending_dividend_value --exact --show-output
+#[test]
+fn test_get_pending_dividend_value() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        assert_eq!(
+            SubtensorModule::get_pending_dividend_value(&coldkey, &hotkey, netuid),
+            0
+        );
+
+        // A quarter of the hotkey alpha earns a quarter of its 4M alpha dividends
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            netuid,
+            1_000_000_000,
+        );
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid, 4_000_000_000);
+        AlphaDividendsPerSubnet::<Test>::insert(netuid, hotkey, 4_000_000);
+
+        // 1M alpha at a price of 2.0, less a negligible slippage
+        let value = SubtensorModule::get_pending_dividend_value(&coldkey, &hotkey, netuid);
+        assert_eq!(
+            Some(value),
+            SubtensorModule::sim_swap_alpha_for_tao(netuid, 1_000_000)
+        );
+        assert_abs_diff_eq!(value, 2_000_000, epsilon = 100);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_stake_weights_for_uids --exact --show-output
+#[test]
+fn test_get_stake_weights_for_uids() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let root_netuid = SubtensorModule::get_root_netuid();
+        add_network(netuid, 1, 0);
+        add_network(root_netuid, 1, 0);
+        for i in 0..3_u64 {
+            let hotkey = U256::from(10 + i);
+            let coldkey = U256::from(20 + i);
+            register_ok_neuron(netuid, hotkey, coldkey, i);
+            SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                netuid,
+                1_000_000 * (i + 1),
+            );
+            SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                root_netuid,
+                3_000_000 * (3 - i),
+            );
+        }
+        SubtensorModule::set_tao_weight(u64::MAX / 4);
+
+        // Matches the full vector for the requested UIDs, skipping unregistered ones
+        let (total_stake, _, _) = SubtensorModule::get_stake_weights_for_network(netuid);
+        let weights = SubtensorModule::get_stake_weights_for_uids(netuid, vec![2, 7, 0]);
+        assert_eq!(
+            weights,
+            vec![
+                (2, total_stake.get(2).copied().unwrap_or_default()),
+                (0, total_stake.first().copied().unwrap_or_default()),
+            ]
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_fee_redistributed_to_stakers --exact --show-output
+#[test]
+fn test_fee_redistributed_to_stakers() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let staker = U256::from(3);
+        let coldkey = U256::from(4);
+        let netuid = add_dynamic_network(&hotkey, &owner_coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let fee = DefaultStakingFee::<Test>::get();
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &staker,
+            netuid,
+            100_000_000_000,
+        );
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+
+        // Disabled by default: the fee goes to the reserves
+        let stake_before =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &staker, netuid);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &staker, netuid),
+            stake_before
+        );
+
+        // Enabled: the hotkey's stakers receive the alpha bought with the fee
+        SubtensorModule::set_fee_redistribution_enabled(netuid, true);
+        let hotkey_alpha_before = TotalHotkeyAlpha::<Test>::get(hotkey, netuid);
+        let tao_before = SubnetTAO::<Test>::get(netuid);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+        let (_, tao_staked, alpha_staked, actual_fee, _) =
+            SubtensorModule::get_last_stake_result(&coldkey, &hotkey).unwrap();
+        assert_eq!(actual_fee, fee);
+        assert_eq!(
+            SubnetTAO::<Test>::get(netuid),
+            tao_before + tao_staked + fee
+        );
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &staker, netuid)
+                > stake_before
+        );
+        // At a price of ~1.0 the stakers grow by ~the fee on top of the staked alpha
+        assert_abs_diff_eq!(
+            TotalHotkeyAlpha::<Test>::get(hotkey, netuid),
+            hotkey_alpha_before + alpha_staked + fee,
+            epsilon = fee / 100
+        );
+        assert!(System::events().iter().any(|e| matches!(
+            e.event,
+            RuntimeEvent::SubtensorModule(Event::FeeRedistributedToStakers(..))
+        )));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_can_absorb_stake --exact --show-output
+#[test]
+fn test_can_absorb_stake() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000);
+        SubtensorModule::set_min_pool_liquidity_override(netuid, Some(500_000_000));
+
+        // Halving the alpha reserve is still fine
+        assert!(SubtensorModule::can_absorb_stake(netuid, 1_000_000_000));
+        // Taking it further below the minimum liquidity is not
+        assert!(!SubtensorModule::can_absorb_stake(netuid, 2_000_000_000));
+
+        // Stable subnets always absorb stake
+        SubnetMechanism::<Test>::insert(netuid, 0);
+        assert!(SubtensorModule::can_absorb_stake(netuid, 2_000_000_000));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_reserve_divergence --exact --show-output
+#[test]
+fn test_get_reserve_divergence() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Price 4.0 against an initial 1.0: 2 * sqrt(4) / (1 + 4) - 1 = -0.2
+        SubnetTAO::<Test>::insert(netuid, 400_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        assert_abs_diff_eq!(
+            SubtensorModule::get_reserve_divergence(netuid, U96F32::from_num(1)).to_num::<f64>(),
+            -0.2,
+            epsilon = 0.000_01
+        );
+
+        // A fall to a quarter of the initial price loses the same
+        assert_abs_diff_eq!(
+            SubtensorModule::get_reserve_divergence(netuid, U96F32::from_num(16)).to_num::<f64>(),
+            -0.2,
+            epsilon = 0.000_01
+        );
+
+        // No divergence without a price move
+        assert_abs_diff_eq!(
+            SubtensorModule::get_reserve_divergence(netuid, U96F32::from_num(4)).to_num::<f64>(),
+            0.0,
+            epsilon = 0.000_01
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_top_stakers --exact --show-output
+#[test]
+fn test_get_top_stakers() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let other_netuid = add_dynamic_network(&U256::from(3), &U256::from(4));
+        let hotkey_a = U256::from(10);
+        let hotkey_b = U256::from(11);
+        let small = U256::from(20);
+        let whale = U256::from(21);
+        let medium = U256::from(22);
+
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_a, &small, netuid, 1_000,
+        );
+        // The whale's stake is summed across hotkeys
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_a, &whale, netuid, 2_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_b, &whale, netuid, 2_000,
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_b, &medium, netuid, 3_000,
+        );
+        // Stake on other subnets is ignored
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_a,
+            &small,
+            other_netuid,
+            10_000,
+        );
+
+        assert_eq!(
+            SubtensorModule::get_top_stakers(netuid, 10),
+            vec![(whale, 4_000), (medium, 3_000), (small, 1_000)]
+        );
+        assert_eq!(
+            SubtensorModule::get_top_stakers(netuid, 2),
+            vec![(whale, 4_000), (medium, 3_000)]
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_stake_into_subnet_reverts_uncredited_swap --exact --show-output
+#[test]
+fn test_stake_into_subnet_reverts_uncredited_swap() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&hotkey, &coldkey);
+        let tao = 2_000_000_000;
+        let fee = 50_000;
+
+        // The swap returns more alpha than the share pool can represent, so no stake is credited
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, u64::MAX - 1);
+        SubnetAlphaOut::<Test>::insert(netuid, 0);
+        assert!(
+            SubtensorModule::sim_swap_tao_for_alpha(netuid, tao - fee).unwrap() > i64::MAX as u64
+        );
+        let total_stake_before = TotalStake::<Test>::get();
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+        System::reset_events();
+
+        assert_eq!(
+            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, tao, fee),
+            Err(Error::<Test>::StakeNotCredited)
+        );
+
+        // The reserves are restored without the fee
+        assert_eq!(SubnetTAO::<Test>::get(netuid), 1_000_000_000);
+        assert_eq!(SubnetAlphaIn::<Test>::get(netuid), u64::MAX - 1);
+        assert_eq!(SubnetAlphaOut::<Test>::get(netuid), 0);
+        assert_eq!(TotalStake::<Test>::get(), total_stake_before);
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid),
+            0
+        );
+
+        // Nothing is refunded, the caller's transaction rolls back the withdrawal
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            balance_before
+        );
+
+        // The stake is neither cached nor reported
+        assert!(LastStakeResult::<Test>::get(coldkey, hotkey).is_none());
+        assert!(!System::events().iter().any(|e| matches!(
+            e.event,
+            RuntimeEvent::SubtensorModule(Event::StakeAdded(..))
+        )));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_minimum_yield_to_offset_fees --exact --show-output
+#[test]
+fn test_minimum_yield_to_offset_fees() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000_000);
+        assert_eq!(
+            SubtensorModule::minimum_yield_to_offset_fees(&hotkey, netuid),
+            U96F32::from_num(0)
+        );
+
+        // The hotkey paid 0.1% of its alpha as dividends last epoch
+        let stake = 1_000_000_000_000;
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, stake,
+        );
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid, stake);
+        AlphaDividendsPerSubnet::<Test>::insert(netuid, hotkey, stake / 1_000);
+
+        // The dynamic fee charges one epoch of dividends, so that is the break even yield
+        assert_abs_diff_eq!(
+            SubtensorModule::minimum_yield_to_offset_fees(&hotkey, netuid).to_num::<f64>(),
+            0.001,
+            epsilon = 0.000_001
+        );
+
+        // With the dynamic fee disabled only the flat fee has to be covered
+        DynamicFeeEnabled::<Test>::insert(netuid, false);
+        let tao_value = SubtensorModule::sim_swap_alpha_for_tao(netuid, stake).unwrap();
+        assert_abs_diff_eq!(
+            SubtensorModule::minimum_yield_to_offset_fees(&hotkey, netuid).to_num::<f64>(),
+            DefaultStakingFee::<Test>::get() as f64 / tao_value as f64,
+            epsilon = 0.000_000_1
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_remove_stake_all_for_subnet --exact --show-output
+#[test]
+fn test_remove_stake_all_for_subnet() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let coldkey = U256::from(4);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10 + 7_777;
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 3_000_000_000_000);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+
+        // An odd amount at an odd price
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            amount
+        ));
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid)
+                > 0
+        );
+
+        assert_ok!(SubtensorModule::remove_stake_all_for_subnet(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid
+        ));
+
+        // No residual stake or shares are left
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid),
+            0
+        );
+        assert_eq!(
+            Alpha::<Test>::get((hotkey, coldkey, netuid)),
+            U64F64::from_num(0)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_cross_subnet_price --exact --show-output
+#[test]
+fn test_get_cross_subnet_price() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid_a = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let netuid_b = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // A is priced at 2.0 and B at 0.5, so one A is worth four B
+        SubnetTAO::<Test>::insert(netuid_a, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid_a, 100_000_000_000);
+        SubnetTAO::<Test>::insert(netuid_b, 50_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid_b, 100_000_000_000);
+        assert_eq!(
+            SubtensorModule::get_cross_subnet_price(netuid_a, netuid_b),
+            U96F32::from_num(4)
+        );
+        assert_eq!(
+            SubtensorModule::get_cross_subnet_price(netuid_b, netuid_a),
+            U96F32::from_num(0.25)
+        );
+        assert_eq!(
+            SubtensorModule::get_cross_subnet_price(netuid_a, netuid_a),
+            U96F32::from_num(1)
+        );
+
+        // A subnet without a price cannot denominate another
+        SubnetAlphaIn::<Test>::insert(netuid_b, 0);
+        assert_eq!(
+            SubtensorModule::get_cross_subnet_price(netuid_a, netuid_b),
+            U96F32::from_num(0)
+        );
+        assert_eq!(
+            SubtensorModule::get_cross_subnet_price(netuid_b, netuid_a),
+            U96F32::from_num(0)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_subnets_by_liquidity --exact --show-output
+#[test]
+fn test_get_subnets_by_liquidity() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let shallow = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let deep = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let medium = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let stable: u16 = 100;
+        add_network(stable, 1, 0);
+
+        SubnetTAO::<Test>::insert(shallow, 10_000_000_000);
+        SubnetTAO::<Test>::insert(deep, 1_000_000_000_000);
+        SubnetTAO::<Test>::insert(medium, 100_000_000_000);
+        SubnetTAO::<Test>::insert(stable, 5_000_000_000_000);
+
+        // The stable subnet is skipped despite its reserve
+        assert_eq!(
+            SubtensorModule::get_subnets_by_liquidity(true),
+            vec![
+                (deep, 1_000_000_000_000),
+                (medium, 100_000_000_000),
+                (shallow, 10_000_000_000)
+            ]
+        );
+        assert_eq!(
+            SubtensorModule::get_subnets_by_liquidity(false),
+            vec![
+                (shallow, 10_000_000_000),
+                (medium, 100_000_000_000),
+                (deep, 1_000_000_000_000)
+            ]
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_subnet_tao_dominance --exact --show-output
+#[test]
+fn test_get_subnet_tao_dominance() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid_a = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let netuid_b = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Nothing staked
+        TotalStake::<Test>::put(0);
+        assert_eq!(
+            SubtensorModule::get_subnet_tao_dominance(netuid_a),
+            U96F32::from_num(0)
+        );
+
+        // The two reserves hold 160 of the 200 TAO staked
+        SubnetTAO::<Test>::insert(netuid_a, 120_000_000_000);
+        SubnetTAO::<Test>::insert(netuid_b, 40_000_000_000);
+        TotalStake::<Test>::put(200_000_000_000);
+
+        let dominance_a = SubtensorModule::get_subnet_tao_dominance(netuid_a);
+        let dominance_b = SubtensorModule::get_subnet_tao_dominance(netuid_b);
+        assert_abs_diff_eq!(dominance_a.to_num::<f64>(), 0.6, epsilon = 0.000_001);
+        assert_abs_diff_eq!(dominance_b.to_num::<f64>(), 0.2, epsilon = 0.000_001);
+        assert_abs_diff_eq!(
+            (dominance_a + dominance_b).to_num::<f64>(),
+            160.0 / 200.0,
+            epsilon = 0.000_001
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_reserves_in_tao --exact --show-output
+#[test]
+fn test_get_reserves_in_tao() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        SubnetTAO::<Test>::insert(netuid, 150_500_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 250_000_000);
+
+        let (tao, alpha) = SubtensorModule::get_reserves_in_tao(netuid);
+        assert_eq!(tao, U96F32::from_num(150.5));
+        assert_eq!(alpha, U96F32::from_num(0.25));
+        assert_eq!(
+            tao * U96F32::from_num(RAO_PER_TAO),
+            U96F32::from_num(SubnetTAO::<Test>::get(netuid))
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_max_stake_within_slippage --exact --show-output
+#[test]
+fn test_max_stake_within_slippage() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Price is 1.5
+        SubnetTAO::<Test>::insert(netuid, 150_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+
+        // 1% slippage allows 1% of the TAO reserve
+        let amount = SubtensorModule::max_stake_within_slippage(netuid, 100);
+        assert_abs_diff_eq!(amount, 1_500_000_000, epsilon = 100);
+
+        // The amount executes at about 1% above the spot price
+        let alpha = SubtensorModule::sim_swap_tao_for_alpha(netuid, amount).unwrap();
+        let slippage = (amount as f64 / alpha as f64) / 1.5 - 1.0;
+        assert_abs_diff_eq!(slippage, 0.01, epsilon = 0.00001);
+
+        // No slippage budget, no stake
+        assert_eq!(SubtensorModule::max_stake_within_slippage(netuid, 0), 0);
+
+        // Stable subnets have no slippage
+        SubnetMechanism::<Test>::insert(netuid, 0);
+        assert_eq!(
+            SubtensorModule::max_stake_within_slippage(netuid, 100),
+            u64::MAX
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_price_elasticity --exact --show-output
+#[test]
+fn test_get_price_elasticity() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let deep_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let shallow_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Both pools are priced at 1.0
+        SubnetTAO::<Test>::insert(deep_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(deep_netuid, 1_000_000_000_000);
+        SubnetTAO::<Test>::insert(shallow_netuid, 10_000_000_000);
+        SubnetAlphaIn::<Test>::insert(shallow_netuid, 10_000_000_000);
+
+        // 2 / alpha per rao: 0.002 and 0.2 per TAO
+        let deep = SubtensorModule::get_price_elasticity(deep_netuid);
+        let shallow = SubtensorModule::get_price_elasticity(shallow_netuid);
+        assert!(deep < shallow);
+        assert_abs_diff_eq!(deep.to_num::<f64>(), 0.002, epsilon = 0.000001);
+        assert_abs_diff_eq!(shallow.to_num::<f64>(), 0.2, epsilon = 0.000001);
+
+        // The derivative matches the price move of a small stake
+        let tao_in = 10_000_000;
+        let alpha_out = SubtensorModule::sim_swap_tao_for_alpha(shallow_netuid, tao_in).unwrap();
+        let price_after = (10_000_000_000 + tao_in) as f64 / (10_000_000_000 - alpha_out) as f64;
+        assert_abs_diff_eq!(
+            (price_after - 1.0) / 0.01,
+            shallow.to_num::<f64>(),
+            epsilon = 0.001
+        );
+
+        // Root and stable subnets do not move
+        assert_eq!(
+            SubtensorModule::get_price_elasticity(SubtensorModule::get_root_netuid()),
+            U96F32::from_num(0)
+        );
+        SubnetMechanism::<Test>::insert(deep_netuid, 0);
+        assert_eq!(
+            SubtensorModule::get_price_elasticity(deep_netuid),
+            U96F32::from_num(0)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_default_slippage --exact --show-output
+#[test]
+fn test_default_slippage() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 100_000_000_000);
+
+        // Tolerances above 100% are rejected
+        assert_noop!(
+            SubtensorModule::set_default_slippage(RuntimeOrigin::signed(coldkey), Some(10_001)),
+            Error::<Test>::InvalidSlippageTolerance
+        );
+
+        // Without a default, the whole amount is allowed
+        assert_eq!(
+            SubtensorModule::get_default_max_amount(&coldkey, netuid, 10_000_000_000, true),
+            10_000_000_000
+        );
+
+        // A 1% tolerance allows buying up to 101 * 1.0 - 100 = 1 TAO
+        assert_ok!(SubtensorModule::set_default_slippage(
+            RuntimeOrigin::signed(coldkey),
+            Some(100)
+        ));
+        assert_eq!(DefaultSlippageBps::<Test>::get(coldkey), Some(100));
+        assert_eq!(
+            SubtensorModule::get_default_max_amount(&coldkey, netuid, 10_000_000_000, true),
+            1_000_000_000
+        );
+        assert_noop!(
+            SubtensorModule::add_stake(
+                RuntimeOrigin::signed(coldkey),
+                owner_hotkey,
+                netuid,
+                10_000_000_000
+            ),
+            Error::<Test>::SlippageTooHigh
+        );
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            owner_hotkey,
+            netuid,
+            500_000_000
+        ));
+
+        // Other coldkeys are unaffected
+        assert_eq!(
+            SubtensorModule::get_default_max_amount(&owner_coldkey, netuid, 10_000_000_000, true),
+            10_000_000_000
+        );
+
+        // Clearing the default restores the absolute path
+        assert_ok!(SubtensorModule::set_default_slippage(
+            RuntimeOrigin::signed(coldkey),
+            None
+        ));
+        assert_eq!(DefaultSlippageBps::<Test>::get(coldkey), None);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            owner_hotkey,
+            netuid,
+            10_000_000_000
+        ));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_stake_for_target_dividend_share --exact --show-output
+#[test]
+fn test_stake_for_target_dividend_share() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // A hotkey without alpha is owned by any stake
+        assert_eq!(
+            SubtensorModule::stake_for_target_dividend_share(&owner_hotkey, netuid, 2_500),
+            0
+        );
+
+        // 25% of a pool with 300 alpha needs 300 * 0.25 / 0.75 = 100 alpha
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &owner_coldkey,
+            netuid,
+            300_000_000_000,
+        );
+        let needed = SubtensorModule::stake_for_target_dividend_share(&owner_hotkey, netuid, 2_500);
+        assert_eq!(needed, 100_000_000_000);
+
+        // Staking it owns the target share of the hotkey
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &coldkey,
+            netuid,
+            needed,
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &owner_hotkey,
+                &coldkey,
+                netuid
+            ),
+            100_000_000_000
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_on_subnet(&owner_hotkey, netuid),
+            400_000_000_000
+        );
+
+        // The whole pool cannot be bought
+        assert_eq!(
+            SubtensorModule::stake_for_target_dividend_share(&owner_hotkey, netuid, 10_000),
+            u64::MAX
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_swap_audit --exact --show-output
+#[test]
+fn test_swap_audit() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let amount = 1_000_000_000;
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 2);
+        let block = SubtensorModule::get_current_block_as_u64();
+
+        // Nothing is recorded by default
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            owner_hotkey,
+            netuid,
+            amount
+        ));
+        assert_eq!(SubtensorModule::get_swap_record(block, 0), None);
+
+        // An audited swap records the reserves around it
+        SubtensorModule::set_swap_audit_enabled(true);
+        let tao_reserve_before = SubnetTAO::<Test>::get(netuid);
+        let alpha_reserve_before = SubnetAlphaIn::<Test>::get(netuid);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            owner_hotkey,
+            netuid,
+            amount
+        ));
+        let (_, tao_staked, alpha, fee, _) =
+            SubtensorModule::get_last_stake_result(&coldkey, &owner_hotkey).unwrap();
+        let record = SubtensorModule::get_swap_record(block, 0).unwrap();
+        assert_eq!(
+            record,
+            SwapRecord {
+                netuid,
+                is_buy: true,
+                tao_reserve_before,
+                alpha_reserve_before,
+                tao_reserve_after: tao_reserve_before + tao_staked,
+                alpha_reserve_after: alpha_reserve_before - alpha,
+                amount_in: tao_staked,
+                amount_out: alpha,
+                fee,
+            }
+        );
+        assert_eq!(SwapAuditCount::<Test>::get(block), 1);
+
+        // Records are pruned once they fall out of the retention
+        SubtensorModule::set_swap_audit_retention(10);
+        SubtensorModule::prune_swap_audit(block + 9);
+        assert!(SubtensorModule::get_swap_record(block, 0).is_some());
+        SubtensorModule::prune_swap_audit(block + 10);
+        assert_eq!(SubtensorModule::get_swap_record(block, 0), None);
+        assert_eq!(SwapAuditCount::<Test>::get(block), 0);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_prune_swap_audit_after_lowering_retention --exact --show-output
+#[test]
+fn test_prune_swap_audit_after_lowering_retention() {
+    new_test_ext(1).execute_with(|| {
+        let record = SwapRecord {
+            netuid: 1,
+            is_buy: true,
+            tao_reserve_before: 1,
+            alpha_reserve_before: 1,
+            tao_reserve_after: 1,
+            alpha_reserve_after: 1,
+            amount_in: 0,
+            amount_out: 0,
+            fee: 0,
+        };
+        for block in 100..200_u64 {
+            SwapAudit::<Test>::insert(block, 0, record.clone());
+            SwapAudit::<Test>::insert(block, 1, record.clone());
+            SwapAuditCount::<Test>::insert(block, 2);
+        }
+        SubtensorModule::set_swap_audit_retention(1000);
+        SubtensorModule::prune_swap_audit(1100);
+        assert_eq!(SwapAuditPrunedUntil::<Test>::get(), 101);
+        assert!(SubtensorModule::get_swap_record(150, 0).is_some());
+
+        // Lowering the retention prunes every block up to the new cutoff, a bounded number per call
+        SubtensorModule::set_swap_audit_retention(10);
+        SubtensorModule::prune_swap_audit(1101);
+        assert_eq!(
+            SwapAuditPrunedUntil::<Test>::get(),
+            101 + SWAP_AUDIT_PRUNE_BLOCKS
+        );
+        assert!(SubtensorModule::get_swap_record(199, 1).is_some());
+        SubtensorModule::prune_swap_audit(1102);
+        for block in 100..200_u64 {
+            assert_eq!(SubtensorModule::get_swap_record(block, 0), None);
+            assert_eq!(SubtensorModule::get_swap_record(block, 1), None);
+            assert_eq!(SwapAuditCount::<Test>::get(block), 0);
+        }
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_effective_transition_price --exact --show-output
+#[test]
+fn test_get_effective_transition_price() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let origin_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let dest_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let alpha = 10_000_000_000;
+
+        // Origin is priced at 2.0 and destination at 1.0
+        SubnetTAO::<Test>::insert(origin_netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(origin_netuid, 100_000_000_000);
+        SubnetTAO::<Test>::insert(dest_netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(dest_netuid, 100_000_000_000);
+        let amm_price = SubtensorModule::get_cross_subnet_price(origin_netuid, dest_netuid);
+        assert_eq!(amm_price, U96F32::from_num(2));
+
+        // Slippage alone makes the move cheaper than the AMM price
+        FeeHolidayUntil::<Test>::insert(origin_netuid, u64::MAX);
+        let slippage_only = SubtensorModule::get_effective_transition_price(
+            &owner_hotkey,
+            origin_netuid,
+            dest_netuid,
+            alpha,
+        );
+        assert!(slippage_only < amm_price);
+
+        // The fee lowers it further
+        FeeHolidayUntil::<Test>::remove(origin_netuid);
+        let all_in = SubtensorModule::get_effective_transition_price(
+            &owner_hotkey,
+            origin_netuid,
+            dest_netuid,
+            alpha,
+        );
+        assert!(all_in > U96F32::from_num(0));
+        assert!(all_in < slippage_only);
+
+        // A move too small to restake has no price
+        assert_eq!(
+            SubtensorModule::get_effective_transition_price(
+                &owner_hotkey,
+                origin_netuid,
+                dest_netuid,
+                1
+            ),
+            U96F32::from_num(0)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_multiple_skip_missing --exact --show-output
+#[test]
+fn test_add_stake_multiple_skip_missing() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let other_hotkey = U256::from(3);
+        let missing_hotkey = U256::from(4);
+        let coldkey = U256::from(5);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        register_ok_neuron(netuid, other_hotkey, owner_coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, amount * 3);
+
+        assert_ok!(SubtensorModule::add_stake_multiple_skip_missing(
+            RuntimeOrigin::signed(coldkey),
+            vec![
+                (owner_hotkey, netuid, amount),
+                (missing_hotkey, netuid, amount),
+                (other_hotkey, netuid, amount),
+            ]
+            .try_into()
+            .unwrap()
+        ));
+
+        // The registered hotkeys are staked to
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &owner_hotkey,
+                &coldkey,
+                netuid
+            ) > 0
+        );
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &other_hotkey,
+                &coldkey,
+                netuid
+            ) > 0
+        );
+
+        // The missing hotkey is skipped and its TAO stays with the coldkey
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &missing_hotkey,
+                &coldkey,
+                netuid
+            ),
+            0
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), amount);
+        assert!(System::events().iter().any(|e| {
+            e.event
+                == RuntimeEvent::SubtensorModule(Event::StakeSkippedMissingHotkey(
+                    coldkey,
+                    missing_hotkey,
+                    netuid,
+                    amount,
+                ))
+        }));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_position_cost_apy --exact --show-output
+#[test]
+fn test_get_position_cost_apy() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // No position, no cost
+        assert_eq!(
+            SubtensorModule::get_position_cost_apy(&owner_hotkey, &coldkey, netuid),
+            U96F32::from_num(0)
+        );
+
+        // 10 alpha in a pool of 10 TAO and 10 alpha sells for 10 - 100 / 20 = 5 TAO, half of
+        // its spot value
+        SubnetTAO::<Test>::insert(netuid, 10_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 10_000_000_000);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &owner_hotkey,
+            &coldkey,
+            netuid,
+            10_000_000_000,
+        );
+        let shallow_cost = SubtensorModule::get_position_cost_apy(&owner_hotkey, &coldkey, netuid);
+        assert!(shallow_cost > U96F32::from_num(0.5));
+        assert!(shallow_cost < U96F32::from_num(0.51));
+
+        // The same position in a deep pool costs little more than the fee
+        SubnetTAO::<Test>::insert(netuid, 10_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 10_000_000_000_000);
+        let deep_cost = SubtensorModule::get_position_cost_apy(&owner_hotkey, &coldkey, netuid);
+        assert!(deep_cost > U96F32::from_num(0));
+        assert!(deep_cost < U96F32::from_num(0.01));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_price_index_zero_liquidity_policy --exact --show-output
+#[test]
+fn test_price_index_zero_liquidity_policy() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid1 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let netuid2 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let empty_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+
+        // Prices 1.0 and 2.0 with 100 TAO each, and an empty pool that last traded around 4.0
+        SubnetTAO::<Test>::insert(netuid1, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid1, 100_000_000_000);
+        SubnetTAO::<Test>::insert(netuid2, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid2, 50_000_000_000);
+        SubnetTAO::<Test>::insert(empty_netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(empty_netuid, 0);
+        SubnetMovingPrice::<Test>::insert(empty_netuid, I96F32::from_num(4));
+
+        // Skip: (1.0 * 100 + 2.0 * 100) / 200 = 1.5, median 1.0
+        assert_abs_diff_eq!(
+            SubtensorModule::get_global_alpha_index(false, ZeroLiquidityPolicy::default())
+                .to_num::<f64>(),
+            1.5,
+            epsilon = 0.000001
+        );
+        assert_abs_diff_eq!(
+            SubtensorModule::get_weighted_median_price(ZeroLiquidityPolicy::default())
+                .to_num::<f64>(),
+            1.0,
+            epsilon = 0.000001
+        );
+
+        // TreatAsZero: (100 + 200 + 0) / 400 = 0.75, and the empty pool holds half the reserves
+        assert_abs_diff_eq!(
+            SubtensorModule::get_global_alpha_index(false, ZeroLiquidityPolicy::TreatAsZero)
+                .to_num::<f64>(),
+            0.75,
+            epsilon = 0.000001
+        );
+        assert_eq!(
+            SubtensorModule::get_weighted_median_price(ZeroLiquidityPolicy::TreatAsZero),
+            U96F32::from_num(0)
+        );
+
+        // TreatAsPrevious: (100 + 200 + 800) / 400 = 2.75, median 2.0
+        assert_abs_diff_eq!(
+            SubtensorModule::get_global_alpha_index(false, ZeroLiquidityPolicy::TreatAsPrevious)
+                .to_num::<f64>(),
+            2.75,
+            epsilon = 0.000001
+        );
+        assert_abs_diff_eq!(
+            SubtensorModule::get_weighted_median_price(ZeroLiquidityPolicy::TreatAsPrevious)
+                .to_num::<f64>(),
+            2.0,
+            epsilon = 0.000001
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_min_unstakeable_alpha --exact --show-output
+#[test]
+fn test_min_unstakeable_alpha() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let deep_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let shallow_netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let min_stake = DefaultMinStake::<Test>::get();
+
+        // Both pools are priced at 1.0
+        SubnetTAO::<Test>::insert(deep_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(deep_netuid, 1_000_000_000_000);
+        SubnetTAO::<Test>::insert(shallow_netuid, 100_000_000);
+        SubnetAlphaIn::<Test>::insert(shallow_netuid, 100_000_000);
+
+        for netuid in [deep_netuid, shallow_netuid] {
+            let alpha = SubtensorModule::min_unstakeable_alpha(netuid);
+            assert!(SubtensorModule::sim_swap_alpha_for_tao(netuid, alpha).unwrap() > min_stake);
+            assert!(
+                SubtensorModule::sim_swap_alpha_for_tao(netuid, alpha - 1).unwrap() <= min_stake
+            );
+        }
+
+        // Slippage in the shallow pool raises the minimum: 1e16 / (1e8 - 5e5) - 1e8 ~ 502_513
+        let deep_alpha = SubtensorModule::min_unstakeable_alpha(deep_netuid);
+        let shallow_alpha = SubtensorModule::min_unstakeable_alpha(shallow_netuid);
+        assert!(deep_alpha < shallow_alpha);
+        assert_abs_diff_eq!(shallow_alpha, 502_513, epsilon = 1);
+
+        // Stable subnets swap 1:1
+        SubnetMechanism::<Test>::insert(deep_netuid, 0);
+        assert_eq!(
+            SubtensorModule::min_unstakeable_alpha(deep_netuid),
+            min_stake + 1
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_break_even_holding_blocks --exact --show-output
+#[test]
+fn test_break_even_holding_blocks() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(netuid, 99, 0);
+
+        // No dividends, no break-even
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid, 1_000_000_000);
+        assert_eq!(
+            SubtensorModule::break_even_holding_blocks(&hotkey, &coldkey, netuid, 1_000_000_000),
+            None
+        );
+
+        // 0.1% per epoch of 100 blocks. On a stable subnet 1 TAO receives 999_950_000 alpha after
+        // the entry fee, yielding 9_999.5 per block against fees of 2 * 50_000
+        AlphaDividendsPerSubnet::<Test>::insert(netuid, hotkey, 1_000_000);
+        assert_eq!(
+            SubtensorModule::break_even_holding_blocks(&hotkey, &coldkey, netuid, 1_000_000_000),
+            Some(11)
+        );
+
+        // A larger stake covers the same fixed fees sooner
+        assert_eq!(
+            SubtensorModule::break_even_holding_blocks(&hotkey, &coldkey, netuid, 100_000_000_000),
+            Some(1)
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_get_net_stake_flow --exact --show-output
+#[test]
+fn test_get_net_stake_flow() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid: u16 = add_dynamic_network(&hotkey, &coldkey);
+        let fee = DefaultStakingFee::<Test>::get();
+        let amount = 1_000_000_000;
+
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        assert_eq!(SubtensorModule::get_net_stake_flow(netuid, 7200), 0);
+
+        // Three stakes and one unstake
+        for _ in 0..3 {
+            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount, fee).unwrap();
+        }
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        let tao_out = SubtensorModule::sim_swap_alpha_for_tao(netuid, alpha / 3).unwrap();
+        SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha / 3, fee);
+
+        let flow = SubtensorModule::get_net_stake_flow(netuid, 7200);
+        assert!(flow > 0);
+        assert_eq!(flow, (3 * (amount - fee) - tao_out) as i64);
+
+        // Flow on other subnets is unaffected
+        assert_eq!(SubtensorModule::get_net_stake_flow(netuid + 1, 7200), 0);
+
+        // The flow leaves the window once its interval is older than the window
+        System::set_block_number(System::block_number() + 2 * STAKE_FLOW_INTERVAL);
+        assert_eq!(
+            SubtensorModule::get_net_stake_flow(netuid, STAKE_FLOW_INTERVAL),
+            0
+        );
+        assert_eq!(SubtensorModule::get_net_stake_flow(netuid, 7200), flow);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_min_stakers_for_large_stake --exact --show-output
+#[test]
+fn test_add_stake_min_stakers_for_large_stake() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let whale = U256::from(3);
+        let small_staker = U256::from(10);
+        let netuid: u16 = add_dynamic_network(&hotkey, &owner_coldkey);
+        let large_amount = 200_000_000_000;
+        let small_amount = 1_000_000_000;
+
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+        SubtensorModule::add_balance_to_coldkey_account(&whale, large_amount);
+
+        // Two more stakers are needed before large stakes are accepted
+        let stakers = SubnetStakerCount::<Test>::get(netuid);
+        SubtensorModule::set_min_stakers_for_large_stake(netuid, Some(stakers + 2));
+        assert!(large_amount > SubtensorModule::get_large_stake_threshold());
+        assert_noop!(
+            SubtensorModule::add_stake(RuntimeOrigin::signed(whale), hotkey, netuid, large_amount),
+            Error::<Test>::NotEnoughStakersForLargeStake
+        );
+
+        // Small stakes are not affected and count as stakers
+        for coldkey in [small_staker, U256::from(11)] {
+            SubtensorModule::add_balance_to_coldkey_account(&coldkey, small_amount);
+            assert_ok!(SubtensorModule::add_stake(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                small_amount
+            ));
+        }
+        assert_eq!(SubnetStakerCount::<Test>::get(netuid), stakers + 2);
+
+        // Now the large stake is accepted
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(whale),
+            hotkey,
+            netuid,
+            large_amount
+        ));
+        assert_eq!(SubnetStakerCount::<Test>::get(netuid), stakers + 3);
+
+        // Fully unstaking removes the staker
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &small_staker,
+            netuid,
+        );
+        SubtensorModule::unstake_from_subnet(&hotkey, &small_staker, netuid, alpha, 0);
+        assert_eq!(SubnetStakerCount::<Test>::get(netuid), stakers + 2);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_staker_count_counts_distinct_coldkeys --exact --show-output
+#[test]
+fn test_staker_count_counts_distinct_coldkeys() {
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let other_hotkey = U256::from(4);
+        let netuid: u16 = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let amount = DefaultMinStake::<Test>::get() * 10;
+        let stakers = SubnetStakerCount::<Test>::get(netuid);
+
+        // Staking to two hotkeys counts the coldkey once
+        SubtensorModule::create_account_if_non_existent(&coldkey, &other_hotkey);
+        for hotkey in [owner_hotkey, other_hotkey] {
+            SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount, 0).unwrap();
+        }
+        assert_eq!(SubnetStakerCount::<Test>::get(netuid), stakers + 1);
+        assert_eq!(SubnetColdkeyPositions::<Test>::get(netuid, coldkey), 2);
+
+        // The coldkey stays a staker until its last position is closed
+        for hotkey in [owner_hotkey, other_hotkey] {
+            let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey, &coldkey, netuid,
+            );
+            assert_eq!(SubnetStakerCount::<Test>::get(netuid), stakers + 1);
+            SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha, 0);
+        }
+        assert_eq!(SubnetStakerCount::<Test>::get(netuid), stakers);
+        assert_eq!(SubnetColdkeyPositions::<Test>::get(netuid, coldkey), 0);
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_swap_stake_min_stakers_for_large_stake --exact --show-output
+#[test]
+fn test_swap_stake_min_stakers_for_large_stake() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let origin_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let gated_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let hotkey = U256::from(1);
+        let whale = U256::from(2);
+        let large_amount = 200_000_000_000;
+
+        for netuid in [origin_netuid, gated_netuid] {
+            SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000);
+            SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000);
+        }
+        let stakers = SubnetStakerCount::<Test>::get(gated_netuid);
+        SubtensorModule::set_min_stakers_for_large_stake(gated_netuid, Some(stakers + 1));
+
+        // A large stake on an ungated subnet cannot be swapped into the gated subnet
+        SubtensorModule::create_account_if_non_existent(&whale, &hotkey);
+        SubtensorModule::stake_into_subnet(&hotkey, &whale, origin_netuid, large_amount, 0)
+            .unwrap();
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &whale,
+            origin_netuid,
+        );
+        assert!(
+            SubtensorModule::sim_swap_alpha_for_tao(origin_netuid, alpha).unwrap_or_default()
+                > SubtensorModule::get_large_stake_threshold()
+        );
+        assert_noop!(
+            SubtensorModule::swap_stake(
+                RuntimeOrigin::signed(whale),
+                hotkey,
+                origin_netuid,
+                gated_netuid,
+                alpha
+            ),
+            Error::<Test>::NotEnoughStakersForLargeStake
+        );
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_add_stake_price_ceiling --exact --show-output
+#[test]
+fn test_add_stake_price_ceiling() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid: u16 = add_dynamic_network(&hotkey, &coldkey);
+        let large_amount = 100_000_000_000;
+        let small_amount = 1_000_000_000;
+
+        // Price is 1.0 with a ceiling of 1.5
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        SubtensorModule::set_price_ceiling(netuid, Some(U96F32::from_num(1.5)));
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, large_amount + small_amount);
+
+        // Staking 100 TAO would double the TAO reserve and halve the alpha reserve: price 4.0
+        assert_noop!(
+            SubtensorModule::add_stake(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                netuid,
+                large_amount
+            ),
+            Error::<Test>::PriceCeilingExceeded
+        );
+
+        // A small stake stays below the ceiling
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            small_amount
+        ));
+        assert!(SubtensorModule::get_alpha_price(netuid) < U96F32::from_num(1.5));
+
+        // Without a ceiling the large stake goes through
+        SubtensorModule::set_price_ceiling(netuid, None);
+        assert_ok!(SubtensorModule::add_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            large_amount
+        ));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_swap_stake_price_ceiling --exact --show-output
+#[test]
+fn test_swap_stake_price_ceiling() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let origin_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let destination_netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+
+        // Both prices are 1.0, with a ceiling of 1.5 on the destination
+        SubnetTAO::<Test>::insert(origin_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(origin_netuid, 1_000_000_000_000);
+        SubnetTAO::<Test>::insert(destination_netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(destination_netuid, 100_000_000_000);
+        SubtensorModule::set_price_ceiling(destination_netuid, Some(U96F32::from_num(1.5)));
+
+        SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, origin_netuid, 100_000_000_000, 0)
+            .unwrap();
+        let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            origin_netuid,
+        );
+
+        // Moving about 100 TAO into the destination pool would push its price to about 4.0
+        assert_noop!(
+            SubtensorModule::swap_stake(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                origin_netuid,
+                destination_netuid,
+                alpha
+            ),
+            Error::<Test>::PriceCeilingExceeded
+        );
+
+        // A small swap stays below the ceiling
+        assert_ok!(SubtensorModule::swap_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            origin_netuid,
+            destination_netuid,
+            1_000_000_000
+        ));
+        assert!(SubtensorModule::get_alpha_price(destination_netuid) < U96F32::from_num(1.5));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_cumulative_fees_paid --exact --show-output
+#[test]
+fn test_cumulative_fees_paid() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let other_coldkey = U256::from(3);
+        let netuid: u16 = add_dynamic_network(&hotkey, &coldkey);
+        let amount = 1_000_000_000;
+
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        assert_eq!(
+            SubtensorModule::cumulative_fees_paid(&coldkey, &hotkey, netuid),
+            0
+        );
+
+        // Two stakes and an unstake with different fees
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount, 50_000).unwrap();
+        SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, amount, 70_000).unwrap();
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha / 2, 30_000);
+
+        assert_eq!(
+            SubtensorModule::cumulative_fees_paid(&coldkey, &hotkey, netuid),
+            150_000
+        );
+
+        // Other positions are not charged
+        assert_eq!(
+            SubtensorModule::cumulative_fees_paid(&other_coldkey, &hotkey, netuid),
+            0
+        );
+        assert_eq!(
+            SubtensorModule::cumulative_fees_paid(&coldkey, &hotkey, netuid + 1),
+            0
+        );
+
+        // The total is dropped when the position is fully exited
+        let alpha =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, alpha, 30_000);
+        assert!(!PositionFeesPaid::<Test>::contains_key((
+            coldkey, hotkey, netuid
+        )));
+    });
+}
+
+// cargo test --package pallet-subtensor --lib -- tests::staking::test_pool_invariants_random_sequence --exact --show-output
+#[test]
+fn test_pool_invariants_random_sequence() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    new_test_ext(1).execute_with(|| {
+        let owner_hotkey = U256::from(1);
+        let owner_coldkey = U256::from(2);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let hotkeys = [U256::from(10), U256::from(11)];
+        let coldkeys = [U256::from(20), U256::from(21), U256::from(22)];
+        let fee = DefaultStakingFee::<Test>::get();
+
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 100_000_000_000);
+        assert_pool_invariants(netuid);
+
+        // Constant seed so failures are reproducible
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let hotkey = *hotkeys.get(rng.gen_range(0..hotkeys.len())).unwrap();
+            let coldkey = *coldkeys.get(rng.gen_range(0..coldkeys.len())).unwrap();
+            if rng.gen_bool(0.5) {
+                let tao = rng.gen_range(fee + 1..10_000_000_000);
+                SubtensorModule::stake_into_subnet(&hotkey, &coldkey, netuid, tao, fee).unwrap();
+            } else {
+                let alpha = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                    &hotkey, &coldkey, netuid,
+                );
+                if alpha > 0 {
+                    let unstaked = rng.gen_range(1..=alpha);
+                    SubtensorModule::unstake_from_subnet(&hotkey, &coldkey, netuid, unstaked, fee);
+                }
+            }
+            assert_pool_invariants(netuid);
+        }
+    });
+}
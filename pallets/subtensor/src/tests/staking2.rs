@@ -1,5 +1,6 @@
 use super::mock::*;
 use crate::*;
+use approx::assert_abs_diff_eq;
 use frame_support::{
     assert_ok,
     dispatch::{GetDispatchInfo, Pays},
@@ -946,3 +947,446 @@ fn test_stake_fee_calculation() {
         assert_ne!(stake_fee_8, default_fee);
     });
 }
+
+#[test]
+fn test_stake_fee_holiday() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = 1;
+        let stake_amount = 100_000_000_000_u64;
+        let default_fee = DefaultStakingFee::<Test>::get();
+
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        SubnetAlphaIn::<Test>::insert(netuid, 200_000_000_000);
+        SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+
+        let add_fee = || {
+            SubtensorModule::calculate_staking_fee(
+                None,
+                &coldkey,
+                Some((&hotkey, netuid)),
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+        let remove_fee = || {
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, netuid)),
+                &coldkey,
+                None,
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+
+        // Holiday until block 10
+        SubtensorModule::set_fee_holiday_until(netuid, 10);
+        assert!(SubtensorModule::is_in_fee_holiday(netuid));
+        assert_eq!(add_fee(), 0);
+        assert_eq!(remove_fee(), 0);
+
+        // Fees apply again once the holiday is over
+        run_to_block(10);
+        assert!(!SubtensorModule::is_in_fee_holiday(netuid));
+        assert_eq!(add_fee(), default_fee);
+        assert!(remove_fee() >= default_fee);
+    });
+}
+
+#[test]
+fn test_stake_fee_holiday_on_destination_only() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let origin_netuid = 1;
+        let holiday_netuid = 2;
+        let stake_amount = 100_000_000_000_u64;
+        let default_fee = DefaultStakingFee::<Test>::get();
+
+        for netuid in [origin_netuid, holiday_netuid] {
+            SubnetMechanism::<Test>::insert(netuid, 1);
+            SubnetAlphaIn::<Test>::insert(netuid, 200_000_000_000);
+            SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        }
+        let move_fee = |from: u16, to: u16| {
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, from)),
+                &coldkey,
+                Some((&hotkey, to)),
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+        let fee_before_holiday = move_fee(origin_netuid, holiday_netuid);
+        assert!(fee_before_holiday >= default_fee);
+
+        // A holiday on the destination does not waive the origin fee
+        SubtensorModule::set_fee_holiday_until(holiday_netuid, 10);
+        assert_eq!(move_fee(origin_netuid, holiday_netuid), fee_before_holiday);
+
+        // Moving out of the holiday subnet is fee free
+        assert_eq!(move_fee(holiday_netuid, origin_netuid), 0);
+    });
+}
+
+#[test]
+fn test_alpha_for_tao_injection() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = 1;
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        SubnetTAO::<Test>::insert(netuid, 10_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 5_000_000_000);
+
+        // Matches the swap simulation within liquidity limits
+        for tao in [1_000_000, 1_000_000_000, 10_000_000_000, 100_000_000_000] {
+            assert_eq!(
+                Some(SubtensorModule::alpha_for_tao_injection(netuid, tao)),
+                SubtensorModule::sim_swap_tao_for_alpha(netuid, tao)
+            );
+        }
+
+        // Not gated by the minimum pool liquidity
+        let tao = u64::MAX / 2;
+        assert_eq!(SubtensorModule::sim_swap_tao_for_alpha(netuid, tao), None);
+        assert!(SubtensorModule::alpha_for_tao_injection(netuid, tao) > 0);
+
+        // Stable mechanism is 1:1
+        SubnetMechanism::<Test>::insert(netuid, 0);
+        assert_eq!(
+            SubtensorModule::alpha_for_tao_injection(netuid, 1_000),
+            1_000
+        );
+    });
+}
+
+#[test]
+fn test_tao_for_exact_alpha_round_trip() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = 1;
+        SubnetTAO::<Test>::insert(netuid, 10_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 5_000_000_000);
+
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        for alpha in [1_000, 1_000_000, 100_000_000, 4_000_000_000] {
+            let tao = SubtensorModule::tao_for_exact_alpha(netuid, alpha).unwrap_or_default();
+            let received = SubtensorModule::sim_swap_tao_for_alpha(netuid, tao);
+            assert_abs_diff_eq!(received.unwrap_or_default(), alpha, epsilon = 2);
+        }
+
+        // Pool can't provide alpha below the minimum liquidity
+        assert_eq!(
+            SubtensorModule::tao_for_exact_alpha(netuid, 4_995_000_000),
+            None
+        );
+        assert_eq!(
+            SubtensorModule::tao_for_exact_alpha(netuid, 6_000_000_000),
+            None
+        );
+
+        // Stable mechanism is 1:1
+        SubnetMechanism::<Test>::insert(netuid, 0);
+        assert_eq!(
+            SubtensorModule::tao_for_exact_alpha(netuid, 1_000),
+            Some(1_000)
+        );
+    });
+}
+
+#[test]
+fn test_stake_fee_dynamic_fee_disabled() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid0 = 1;
+        let netuid1 = 2;
+        let stake_amount = 100_000_000_000_u64;
+        let default_fee = DefaultStakingFee::<Test>::get();
+
+        for netuid in [netuid0, netuid1] {
+            SubnetMechanism::<Test>::insert(netuid, 1);
+            SubnetAlphaIn::<Test>::insert(netuid, 200_000_000_000);
+            SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        }
+        AlphaDividendsPerSubnet::<Test>::insert(netuid0, hotkey, 10_000_000_000);
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid0, 100_000_000_000);
+
+        let move_fee = || {
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, netuid0)),
+                &coldkey,
+                Some((&hotkey, netuid1)),
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+
+        // Dividend based fee by default
+        let dynamic_fee = move_fee();
+        assert!(dynamic_fee > default_fee);
+
+        // Flat fee once the subnet opts out
+        SubtensorModule::set_dynamic_fee_enabled(netuid0, false);
+        assert_eq!(move_fee(), default_fee);
+
+        // And back
+        SubtensorModule::set_dynamic_fee_enabled(netuid0, true);
+        assert_eq!(move_fee(), dynamic_fee);
+    });
+}
+
+#[test]
+fn test_min_tao_for_nonzero_alpha() {
+    new_test_ext(1).execute_with(|| {
+        let deep_netuid = 1;
+        let shallow_netuid = 2;
+        let stable_netuid = 3;
+        SubnetMechanism::<Test>::insert(deep_netuid, 1);
+        SubnetTAO::<Test>::insert(deep_netuid, 1_000_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(deep_netuid, 1_000_000_000_000_000);
+        SubnetMechanism::<Test>::insert(shallow_netuid, 1);
+        SubnetTAO::<Test>::insert(shallow_netuid, 1_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(shallow_netuid, 1_000_000_000);
+
+        // Deep pool at price 1.0 needs a single rao
+        let deep_min = SubtensorModule::min_tao_for_nonzero_alpha(deep_netuid);
+        assert!(deep_min <= 2);
+
+        // Shallow pool at price 1000.0 needs about a thousand rao
+        let shallow_min = SubtensorModule::min_tao_for_nonzero_alpha(shallow_netuid);
+        assert_abs_diff_eq!(shallow_min, 1_000, epsilon = 1);
+
+        // The minimum is exact in both pools
+        for (netuid, min) in [(deep_netuid, deep_min), (shallow_netuid, shallow_min)] {
+            assert!(SubtensorModule::sim_swap_tao_for_alpha(netuid, min).unwrap_or_default() > 0);
+            assert_eq!(
+                SubtensorModule::sim_swap_tao_for_alpha(netuid, min - 1).unwrap_or_default(),
+                0
+            );
+        }
+
+        // Stable subnets swap 1:1
+        assert_eq!(SubtensorModule::min_tao_for_nonzero_alpha(stable_netuid), 1);
+    });
+}
+
+#[test]
+fn test_sim_swap_alpha_for_alpha() {
+    new_test_ext(1).execute_with(|| {
+        let origin_netuid = 1;
+        let destination_netuid = 2;
+        let empty_netuid = 3;
+        for netuid in [origin_netuid, destination_netuid, empty_netuid] {
+            SubnetMechanism::<Test>::insert(netuid, 1);
+        }
+
+        // Origin priced at 2.0, destination at 0.5
+        SubnetTAO::<Test>::insert(origin_netuid, 200_000_000_000);
+        SubnetAlphaIn::<Test>::insert(origin_netuid, 100_000_000_000);
+        SubnetTAO::<Test>::insert(destination_netuid, 100_000_000_000);
+        SubnetAlphaIn::<Test>::insert(destination_netuid, 200_000_000_000);
+
+        let alpha = 1_000_000;
+        let tao = SubtensorModule::sim_swap_alpha_for_tao(origin_netuid, alpha).unwrap_or_default();
+        let expected = SubtensorModule::sim_swap_tao_for_alpha(destination_netuid, tao);
+        assert_eq!(
+            SubtensorModule::sim_swap_alpha_for_alpha(origin_netuid, destination_netuid, alpha),
+            expected
+        );
+
+        // Roughly four destination alpha per origin alpha at these prices
+        assert_abs_diff_eq!(
+            expected.unwrap_or_default(),
+            4 * alpha,
+            epsilon = 4 * alpha / 1_000
+        );
+
+        // Either leg without liquidity has no quote
+        assert_eq!(
+            SubtensorModule::sim_swap_alpha_for_alpha(empty_netuid, destination_netuid, alpha),
+            None
+        );
+        assert_eq!(
+            SubtensorModule::sim_swap_alpha_for_alpha(origin_netuid, empty_netuid, alpha),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_stake_fee_unstake_multiplier() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid0 = 1;
+        let netuid1 = 2;
+        let stake_amount = 100_000_000_000_u64;
+        let default_fee = DefaultStakingFee::<Test>::get();
+
+        for netuid in [netuid0, netuid1] {
+            SubnetMechanism::<Test>::insert(netuid, 1);
+            SubnetAlphaIn::<Test>::insert(netuid, 200_000_000_000);
+            SubnetTAO::<Test>::insert(netuid, 100_000_000_000);
+        }
+        AlphaDividendsPerSubnet::<Test>::insert(netuid0, hotkey, 10_000_000_000);
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid0, 100_000_000_000);
+
+        let add_fee = || {
+            SubtensorModule::calculate_staking_fee(
+                None,
+                &coldkey,
+                Some((&hotkey, netuid0)),
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+        let remove_fee = || {
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, netuid0)),
+                &coldkey,
+                None,
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+        let move_fee = || {
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, netuid0)),
+                &coldkey,
+                Some((&hotkey, netuid1)),
+                &coldkey,
+                U96F32::from_num(stake_amount),
+            )
+        };
+        let base_remove_fee = remove_fee();
+        let base_move_fee = move_fee();
+
+        // Doubling the unstake fee only affects pure removals
+        SubtensorModule::set_unstake_fee_multiplier(netuid0, U96F32::from_num(2));
+        assert_eq!(add_fee(), default_fee);
+        assert_abs_diff_eq!(remove_fee(), base_remove_fee * 2, epsilon = 1);
+        assert_eq!(move_fee(), base_move_fee);
+
+        // The fee floor still applies
+        SubtensorModule::set_unstake_fee_multiplier(netuid0, U96F32::from_num(0));
+        assert_eq!(remove_fee(), default_fee);
+    });
+}
+
+#[test]
+fn test_swap_rejects_degenerate_reserves() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = 1;
+        let stable_netuid = 2;
+        SubnetMechanism::<Test>::insert(netuid, 1);
+
+        // A reserve must stay strictly positive on dynamic subnets
+        assert!(SubtensorModule::reserve_remains_positive(
+            netuid, 1_000, 999
+        ));
+        assert!(!SubtensorModule::reserve_remains_positive(
+            netuid, 1_000, 1_000
+        ));
+        assert!(SubtensorModule::reserve_remains_positive(
+            stable_netuid,
+            1_000,
+            1_000
+        ));
+
+        // Without a minimum liquidity, the simulations allow draining a shallow pool
+        SubtensorModule::set_min_pool_liquidity_override(netuid, Some(0));
+        SubnetTAO::<Test>::insert(netuid, 1_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000);
+        SubnetAlphaOut::<Test>::insert(netuid, u64::MAX / 2);
+        assert_eq!(
+            SubtensorModule::sim_swap_alpha_for_tao(netuid, u64::MAX / 2),
+            Some(1_000)
+        );
+        assert_eq!(
+            SubtensorModule::sim_swap_tao_for_alpha(netuid, u64::MAX / 2),
+            Some(1_000)
+        );
+
+        // Selling enough alpha to drain the TAO reserve leaves the pool untouched
+        assert_eq!(SubtensorModule::swap_alpha_for_tao(netuid, u64::MAX / 2), 0);
+        assert_eq!(SubnetTAO::<Test>::get(netuid), 1_000);
+        assert_eq!(SubnetAlphaIn::<Test>::get(netuid), 1_000);
+
+        // Buying with enough TAO to drain the alpha reserve leaves the pool untouched
+        assert_eq!(SubtensorModule::swap_tao_for_alpha(netuid, u64::MAX / 2), 0);
+        assert_eq!(SubnetTAO::<Test>::get(netuid), 1_000);
+        assert_eq!(SubnetAlphaIn::<Test>::get(netuid), 1_000);
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --workspace --test staking2 -- test_get_fee_schedule --exact --nocapture
+#[test]
+fn test_get_fee_schedule() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = 1;
+        FeeHolidayUntil::<Test>::insert(netuid, 100);
+        DynamicFeeEnabled::<Test>::insert(netuid, false);
+        SubtensorModule::set_fee_burn_bps(netuid, 2_500);
+        SubtensorModule::set_unstake_fee_multiplier(netuid, U96F32::from_num(1.5));
+        SubtensorModule::set_fee_redistribution_enabled(netuid, true);
+
+        let schedule = SubtensorModule::get_fee_schedule(netuid);
+        assert_eq!(schedule.netuid.0, netuid);
+        assert_eq!(schedule.default_fee.0, DefaultStakingFee::<Test>::get());
+        assert!(!schedule.dynamic_fee_enabled);
+        assert_eq!(
+            schedule.dynamic_fee_floor_rate,
+            SubtensorModule::get_dynamic_fee_floor_rate()
+        );
+        assert_eq!(schedule.fee_holiday_until.0, 100);
+        assert_eq!(schedule.fee_burn_bps.0, 2_500);
+        assert_eq!(schedule.unstake_fee_multiplier, U96F32::from_num(1.5));
+        assert!(schedule.fee_redistribution_enabled);
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --workspace --test staking2 -- test_preview_fee_stable --exact --nocapture
+#[test]
+fn test_preview_fee_stable() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let netuid = 1;
+        let alpha = 100_000_000_000_000;
+        SubnetMechanism::<Test>::insert(netuid, 1);
+        SubnetTAO::<Test>::insert(netuid, 1_000_000_000_000_000);
+        SubnetAlphaIn::<Test>::insert(netuid, 1_000_000_000_000_000);
+        SubnetAlphaOut::<Test>::insert(netuid, 1_000_000_000_000_000);
+
+        // Without dividends the dynamic fee is the APR floor component
+        let floor_fee = SubtensorModule::preview_fee_stable(&hotkey, netuid, alpha);
+        assert!(floor_fee > DefaultStakingFee::<Test>::get());
+        assert_eq!(
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, netuid)),
+                &coldkey,
+                Some((&hotkey, 2)),
+                &coldkey,
+                U96F32::saturating_from_num(alpha),
+            ),
+            floor_fee
+        );
+
+        // Dividends raise the fee, but leave the preview unchanged
+        AlphaDividendsPerSubnet::<Test>::insert(netuid, hotkey, 1_000_000_000);
+        TotalHotkeyAlphaLastEpoch::<Test>::insert(hotkey, netuid, 1_000_000_000);
+        assert!(
+            SubtensorModule::calculate_staking_fee(
+                Some((&hotkey, netuid)),
+                &coldkey,
+                Some((&hotkey, 2)),
+                &coldkey,
+                U96F32::saturating_from_num(alpha),
+            ) > floor_fee
+        );
+        assert_eq!(
+            SubtensorModule::preview_fee_stable(&hotkey, netuid, alpha),
+            floor_fee
+        );
+    });
+}
@@ -7,7 +7,7 @@ use safe_math::*;
 use sp_core::Get;
 use sp_core::U256;
 use sp_runtime::Saturating;
-use substrate_fixed::types::{I32F32, U96F32};
+use substrate_fixed::types::{I32F32, U64F64, U96F32};
 
 impl<T: Config> Pallet<T> {
     pub fn ensure_subnet_owner_or_root(
@@ -735,6 +735,167 @@ impl<T: Config> Pallet<T> {
         Self::deposit_event(Event::SubnetOwnerHotkeySet(netuid, hotkey.clone()));
     }
 
+    /// Set the block until which staking on a subnet is fee free.
+    ///
+    /// # Arguments
+    ///
+    /// * `netuid` - The unique identifier for the subnet.
+    /// * `block` - The first block at which fees apply again.
+    ///
+    /// # Effects
+    ///
+    /// * Update the FeeHolidayUntil storage.
+    /// * Emits a FeeHolidaySet event.
+    pub fn set_fee_holiday_until(netuid: u16, block: u64) {
+        FeeHolidayUntil::<T>::insert(netuid, block);
+        Self::deposit_event(Event::FeeHolidaySet(netuid, block));
+    }
+
+    pub fn get_fee_holiday_until(netuid: u16) -> u64 {
+        FeeHolidayUntil::<T>::get(netuid)
+    }
+
+    /// Returns true if the subnet is in its fee holiday at the current block.
+    pub fn is_in_fee_holiday(netuid: u16) -> bool {
+        Self::get_current_block_as_u64() < FeeHolidayUntil::<T>::get(netuid)
+    }
+
+    pub fn get_dynamic_fee_enabled(netuid: u16) -> bool {
+        DynamicFeeEnabled::<T>::get(netuid)
+    }
+    pub fn set_dynamic_fee_enabled(netuid: u16, enabled: bool) {
+        DynamicFeeEnabled::<T>::insert(netuid, enabled);
+        Self::deposit_event(Event::DynamicFeeEnabledSet(netuid, enabled));
+    }
+
+    pub fn get_min_share_increment(netuid: u16) -> U64F64 {
+        MinShareIncrement::<T>::get(netuid)
+    }
+    pub fn set_min_share_increment(netuid: u16, min_share_increment: U64F64) {
+        MinShareIncrement::<T>::insert(netuid, min_share_increment);
+        Self::deposit_event(Event::MinShareIncrementSet(netuid, min_share_increment));
+    }
+
+    pub fn get_hotkey_alpha_snapshot_retention() -> u32 {
+        HotkeyAlphaSnapshotRetention::<T>::get()
+    }
+    pub fn set_hotkey_alpha_snapshot_retention(epochs: u32) {
+        HotkeyAlphaSnapshotRetention::<T>::put(epochs);
+        Self::deposit_event(Event::HotkeyAlphaSnapshotRetentionSet(epochs));
+    }
+
+    pub fn get_ema_debug_events() -> bool {
+        EmaDebugEvents::<T>::get()
+    }
+    pub fn set_ema_debug_events(enabled: bool) {
+        EmaDebugEvents::<T>::put(enabled);
+        Self::deposit_event(Event::EmaDebugEventsSet(enabled));
+    }
+
+    pub fn get_swap_audit_enabled() -> bool {
+        SwapAuditEnabled::<T>::get()
+    }
+    pub fn set_swap_audit_enabled(enabled: bool) {
+        SwapAuditEnabled::<T>::put(enabled);
+        Self::deposit_event(Event::SwapAuditEnabledSet(enabled));
+    }
+
+    pub fn get_swap_audit_retention() -> u64 {
+        SwapAuditRetention::<T>::get()
+    }
+    pub fn set_swap_audit_retention(blocks: u64) {
+        SwapAuditRetention::<T>::put(blocks);
+        Self::deposit_event(Event::SwapAuditRetentionSet(blocks));
+    }
+
+    pub fn get_limit_price_epsilon() -> u64 {
+        LimitPriceEpsilon::<T>::get()
+    }
+    pub fn set_limit_price_epsilon(epsilon: u64) {
+        LimitPriceEpsilon::<T>::put(epsilon);
+        Self::deposit_event(Event::LimitPriceEpsilonSet(epsilon));
+    }
+
+    pub fn get_max_fee_share_bps() -> u16 {
+        MaxFeeShareBps::<T>::get()
+    }
+    pub fn set_max_fee_share_bps(max_fee_share_bps: u16) {
+        MaxFeeShareBps::<T>::put(max_fee_share_bps);
+        Self::deposit_event(Event::MaxFeeShareSet(max_fee_share_bps));
+    }
+
+    pub fn get_fee_burn_bps(netuid: u16) -> u16 {
+        FeeBurnBps::<T>::get(netuid)
+    }
+    pub fn set_fee_burn_bps(netuid: u16, burn_bps: u16) {
+        FeeBurnBps::<T>::insert(netuid, burn_bps);
+        Self::deposit_event(Event::FeeBurnBpsSet(netuid, burn_bps));
+    }
+
+    pub fn get_unstake_fee_multiplier(netuid: u16) -> U96F32 {
+        UnstakeFeeMultiplier::<T>::get(netuid)
+    }
+    pub fn set_unstake_fee_multiplier(netuid: u16, multiplier: U96F32) {
+        UnstakeFeeMultiplier::<T>::insert(netuid, multiplier);
+        Self::deposit_event(Event::UnstakeFeeMultiplierSet(netuid, multiplier));
+    }
+
+    pub fn get_stake_cooldown_blocks(netuid: u16) -> u32 {
+        StakeCooldownBlocks::<T>::get(netuid)
+    }
+    pub fn set_stake_cooldown_blocks(netuid: u16, cooldown_blocks: u32) {
+        StakeCooldownBlocks::<T>::insert(netuid, cooldown_blocks);
+        Self::deposit_event(Event::StakeCooldownBlocksSet(netuid, cooldown_blocks));
+    }
+
+    pub fn get_min_pool_liquidity_override(netuid: u16) -> Option<u64> {
+        MinPoolLiquidityOverride::<T>::get(netuid)
+    }
+    pub fn set_min_pool_liquidity_override(netuid: u16, min_liquidity: Option<u64>) {
+        MinPoolLiquidityOverride::<T>::set(netuid, min_liquidity);
+        Self::deposit_event(Event::MinPoolLiquidityOverrideSet(netuid, min_liquidity));
+    }
+
+    pub fn get_force_partial_fills(netuid: u16) -> bool {
+        ForcePartialFills::<T>::get(netuid)
+    }
+    pub fn set_force_partial_fills(netuid: u16, force: bool) {
+        ForcePartialFills::<T>::insert(netuid, force);
+        Self::deposit_event(Event::ForcePartialFillsSet(netuid, force));
+    }
+
+    pub fn get_fee_redistribution_enabled(netuid: u16) -> bool {
+        FeeRedistributionEnabled::<T>::get(netuid)
+    }
+    pub fn set_fee_redistribution_enabled(netuid: u16, enabled: bool) {
+        FeeRedistributionEnabled::<T>::insert(netuid, enabled);
+        Self::deposit_event(Event::FeeRedistributionEnabledSet(netuid, enabled));
+    }
+
+    pub fn get_price_ceiling(netuid: u16) -> Option<U96F32> {
+        PriceCeiling::<T>::get(netuid)
+    }
+    pub fn set_price_ceiling(netuid: u16, ceiling: Option<U96F32>) {
+        PriceCeiling::<T>::set(netuid, ceiling);
+        Self::deposit_event(Event::PriceCeilingSet(netuid, ceiling));
+    }
+
+    pub fn get_min_stakers_for_large_stake(netuid: u16) -> Option<u32> {
+        MinStakersForLargeStake::<T>::get(netuid)
+    }
+    pub fn set_min_stakers_for_large_stake(netuid: u16, min_stakers: Option<u32>) {
+        MinStakersForLargeStake::<T>::set(netuid, min_stakers);
+        Self::deposit_event(Event::MinStakersForLargeStakeSet(netuid, min_stakers));
+    }
+
+    pub fn get_large_stake_threshold() -> u64 {
+        LargeStakeThreshold::<T>::get()
+    }
+    pub fn set_large_stake_threshold(threshold: u64) {
+        LargeStakeThreshold::<T>::put(threshold);
+        Self::deposit_event(Event::LargeStakeThresholdSet(threshold));
+    }
+
     // Get the uid of the Owner Hotkey for a subnet.
     pub fn get_owner_uid(netuid: u16) -> Option<u16> {
         match SubnetOwnerHotkey::<T>::try_get(netuid) {
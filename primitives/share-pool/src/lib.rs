@@ -98,6 +98,16 @@ where
     }
 
     pub fn sim_update_value_for_one(&mut self, update: i64) -> bool {
+        self.sim_update_value_for_one_with_min_shares(update, U64F64::saturating_from_num(0))
+    }
+
+    /// Simulate an update and check that it changes the shares of a key by
+    /// at least `min_shares`. A zero minimum only requires a non-zero change.
+    pub fn sim_update_value_for_one_with_min_shares(
+        &mut self,
+        update: i64,
+        min_shares: U64F64,
+    ) -> bool {
         let shared_value: U64F64 = self.state_ops.get_shared_value();
         let denominator: U64F64 = self.state_ops.get_denominator();
 
@@ -109,7 +119,14 @@ where
             let shares_per_update: I64F64 =
                 self.get_shares_per_update(update, &shared_value, &denominator);
 
+            let abs_shares_per_update: I64F64 = if shares_per_update < 0 {
+                I64F64::saturating_from_num(0).saturating_sub(shares_per_update)
+            } else {
+                shares_per_update
+            };
+
             shares_per_update != 0
+                && U64F64::saturating_from_num(abs_shares_per_update) >= min_shares
         }
     }
 
@@ -392,6 +409,20 @@ mod tests {
         assert_eq!(value, 1000);
     }
 
+    #[test]
+    fn test_sim_update_value_for_one_with_min_shares() {
+        let mock_ops = MockSharePoolDataOperations::new();
+        let mut pool = SharePool::<u16, MockSharePoolDataOperations>::new(mock_ops);
+
+        pool.update_value_for_one(&1, 1000);
+
+        // One share is worth one unit of value
+        assert!(pool.sim_update_value_for_one_with_min_shares(100, U64F64::from_num(100)));
+        assert!(!pool.sim_update_value_for_one_with_min_shares(99, U64F64::from_num(100)));
+        assert!(!pool.sim_update_value_for_one_with_min_shares(-99, U64F64::from_num(100)));
+        assert!(!pool.sim_update_value_for_one_with_min_shares(0, U64F64::from_num(0)));
+    }
+
     #[test]
     fn test_update_value_for_all() {
         let mock_ops = MockSharePoolDataOperations::new();
@@ -36,7 +36,7 @@ use pallet_subtensor::rpc_info::{
     metagraph::{Metagraph, SelectiveMetagraph},
     neuron_info::{NeuronInfo, NeuronInfoLite},
     show_subnet::SubnetState,
-    stake_info::StakeInfo,
+    stake_info::{FeeSchedule, StakeInfo},
     subnet_info::{SubnetHyperparams, SubnetInfo, SubnetInfov2},
 };
 use smallvec::smallvec;
@@ -207,7 +207,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 261,
+    spec_version: 262,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -1049,6 +1049,8 @@ parameter_types! {
     } else {
         7 * 24 * 60 * 60 / 12 // 7 days
     };
+    pub const SubtensorMaxChildren: u32 = 5;
+    pub const SubtensorMaxParents: u32 = 64;
 }
 
 impl pallet_subtensor::Config for Runtime {
@@ -1114,6 +1116,8 @@ impl pallet_subtensor::Config for Runtime {
     type InitialDissolveNetworkScheduleDuration = InitialDissolveNetworkScheduleDuration;
     type InitialEmaPriceHalvingPeriod = InitialEmaPriceHalvingPeriod;
     type DurationOfStartCall = DurationOfStartCall;
+    type MaxChildren = SubtensorMaxChildren;
+    type MaxParents = SubtensorMaxParents;
 }
 
 use sp_runtime::BoundedVec;
@@ -2091,6 +2095,7 @@ impl_runtime_apis! {
 
     }
 
+    #[api_version(2)]
     impl subtensor_custom_rpc_runtime_api::StakeInfoRuntimeApi<Block> for Runtime {
         fn get_stake_info_for_coldkey( coldkey_account: AccountId32 ) -> Vec<StakeInfo<AccountId32>> {
             SubtensorModule::get_stake_info_for_coldkey( coldkey_account )
@@ -2107,6 +2112,10 @@ impl_runtime_apis! {
         fn get_stake_fee( origin: Option<(AccountId32, u16)>, origin_coldkey_account: AccountId32, destination: Option<(AccountId32, u16)>, destination_coldkey_account: AccountId32, amount: u64 ) -> u64 {
             SubtensorModule::get_stake_fee( origin, origin_coldkey_account, destination, destination_coldkey_account, amount )
         }
+
+        fn get_fee_schedule( netuid: u16 ) -> FeeSchedule {
+            SubtensorModule::get_fee_schedule( netuid )
+        }
     }
 
     impl subtensor_custom_rpc_runtime_api::SubnetRegistrationRuntimeApi<Block> for Runtime {